@@ -29,11 +29,27 @@
 //! ```sh
 //! build-channel --nightly my-channel.toml 2023-02-13
 //! ```
+//!
+//! To add the publish date of a channel to the index of dated channels,
+//!
+//! ```sh
+//! build-channel --nightly my-channel.toml 2023-02-13 --index index.toml
+//! ```
+//!
+//! Channels published earlier under `<archive>/YYYY/MM/DD/my-channel.toml` can be added to the
+//! index as well with `--archive-dir <archive>`.
+//!
+//! To sign a channel, writing its detached signature to `my-channel.toml.sig`,
+//!
+//! ```sh
+//! CHANNEL_SIGNING_KEY=<hex-encoded ed25519 seed> build-channel my-channel.toml 2023-02-13
+//! ```
 
 use anyhow::{bail, Result};
 use clap::Parser;
-use component::{Component, Components};
+use component::{decode_hex, encode_hex, Component, Components};
 use once_cell::sync::Lazy;
+use ring::signature::{Ed25519KeyPair, KeyPair};
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use sha2::Digest;
@@ -42,11 +58,14 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::Duration;
 use toml_edit::value;
 use toml_edit::Document;
 
+/// Version of the channel format written, see `fuelup::channel::MANIFEST_VERSION`.
+const MANIFEST_VERSION: u32 = 1;
 const MAX_ATTEMPTS: u32 = 3;
 const INITIAL_RETRY_DELAY_MS: u64 = 1000;
 
@@ -120,6 +139,15 @@ struct Args {
     /// the GitHub run ID
     #[clap(long)]
     pub github_run_id: Option<String>,
+    /// index of dated channels to add the publish date to, created if it doesn't exist
+    #[clap(long)]
+    pub index: Option<String>,
+    /// directory of channels archived as YYYY/MM/DD/<out_file>, whose dates are added to the index
+    #[clap(long, requires = "index")]
+    pub archive_dir: Option<String>,
+    /// hex-encoded ed25519 seed to sign the channel with
+    #[clap(long, env = "CHANNEL_SIGNING_KEY", hide_env_values = true)]
+    pub signing_key: Option<String>,
     /// key-value pairs of components and their versions to include in a channel, eg. forc=0.35.0
     #[clap(value_parser = parse_key_val::<String, Version>)]
     pub packages: Vec<(String, Version)>,
@@ -156,7 +184,6 @@ struct Asset {
 }
 
 fn get_version(component: &Component) -> Result<Version> {
-    let handle = ureq::builder().user_agent("fuelup").build();
     let mut data = Vec::new();
 
     // For monorepo components, we need to filter by component-specific tags
@@ -165,7 +192,7 @@ fn get_version(component: &Component) -> Result<Version> {
         component.repository_name
     );
 
-    let resp = handle.get(&url).call()?;
+    let resp = github::get(&url)?;
 
     resp.into_reader().read_to_end(&mut data)?;
     let releases: Vec<LatestReleaseApiResponse> =
@@ -222,7 +249,7 @@ fn write_nightly_document(document: &mut Document, components: Vec<Component>) -
         *TODAY
     );
 
-    let resp = ureq::get(&nightly_release_url).call()?;
+    let resp = github::get(&nightly_release_url)?;
     resp.into_reader().read_to_end(&mut data)?;
     let release: Release = serde_json::from_str(&String::from_utf8_lossy(&data))?;
 
@@ -284,6 +311,8 @@ fn write_nightly_document(document: &mut Document, components: Vec<Component>) -
 
                         if let Ok(res) = ureq::get(&asset.browser_download_url).call() {
                             res.into_reader().read_to_end(&mut data)?;
+                            document["pkg"][&component.name]["target"][target.to_string()]
+                                ["size"] = value(data.len() as i64);
                             let mut hasher = Sha256::new();
                             hasher.update(data);
                             let actual_hash = format!("{:x}", hasher.finalize());
@@ -332,14 +361,22 @@ fn write_document(
 
         document["pkg"][&component.name] = implicit_table();
         document["pkg"][&component.name]["version"] = value(version.to_string());
+        document["pkg"][&component.name]["release_notes"] = value(format!(
+            "https://github.com/FuelLabs/{}/releases/tag/{}",
+            repo, tag
+        ));
         document["pkg"][&component.name]["target"] = implicit_table();
 
         for target in &component.targets {
             println!("Adding url and hash for target '{}'", &target);
 
             let url = format!(
-                "https://github.com/FuelLabs/{}/releases/download/{}/{}-{}.tar.gz",
-                repo, tag, tarball_prefix, target
+                "https://github.com/FuelLabs/{}/releases/download/{}/{}-{}.{}",
+                repo,
+                tag,
+                tarball_prefix,
+                target,
+                component.archive_extension()
             );
 
             let data = fetch_with_retry(&url)?;
@@ -352,6 +389,8 @@ fn write_document(
             document["pkg"][&component.name]["target"][target.to_string()]["url"] = value(url);
             document["pkg"][&component.name]["target"][target.to_string()]["hash"] =
                 value(actual_hash);
+            document["pkg"][&component.name]["target"][target.to_string()]["size"] =
+                value(data.len() as i64);
         }
     }
     Ok(())
@@ -378,7 +417,7 @@ fn main() -> Result<()> {
     }
 
     println!("writing channel: '{}'", &args.out_file);
-    let mut channel_str = String::new();
+    let mut channel_str = format!("manifest-version = {}\n", MANIFEST_VERSION);
     if let Some(github_run_id) = args.github_run_id {
         channel_str.push_str(&format!(
             "published_by = \"https://github.com/FuelLabs/fuelup/actions/runs/{}\"\n",
@@ -389,13 +428,157 @@ fn main() -> Result<()> {
     channel_str.push_str(&document.to_string());
     fs::write(&args.out_file, &channel_str)?;
 
+    if let Some(signing_key) = args.signing_key {
+        let signature_file = format!("{}.sig", &args.out_file);
+        println!("signing channel: '{}'", &signature_file);
+        fs::write(&signature_file, sign_channel(&signing_key, &channel_str)?)?;
+    }
+
+    if let Some(index_file) = args.index {
+        println!("updating index: '{}'", &index_file);
+        let index = match fs::read_to_string(&index_file) {
+            Ok(index) => Some(index),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let mut dates = match &args.archive_dir {
+            Some(archive_dir) => archived_dates(Path::new(archive_dir), &args.out_file)?,
+            None => Vec::new(),
+        };
+        dates.push(args.publish_date.clone());
+        fs::write(&index_file, add_to_index(index.as_deref(), &dates)?)?;
+    }
+
     Ok(())
 }
 
+/// Returns the channel index `index` with `new_dates` added to its dates, which are kept sorted so
+/// that fuelup can list them as they are. `None` starts a new index.
+fn add_to_index(index: Option<&str>, new_dates: &[String]) -> Result<String> {
+    if let Some(date) = new_dates
+        .iter()
+        .find(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err())
+    {
+        bail!("Publish date '{}' must be formatted as YYYY-MM-DD", date);
+    }
+
+    let mut dates: Vec<String> = match index {
+        Some(index) => index.parse::<Document>()?["dates"]
+            .as_array()
+            .map(|dates| {
+                dates
+                    .iter()
+                    .filter_map(|d| d.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    dates.extend_from_slice(new_dates);
+    dates.sort();
+    dates.dedup();
+
+    let mut index_str = String::from("dates = [\n");
+    for date in dates {
+        index_str.push_str(&format!("    \"{}\",\n", date));
+    }
+    index_str.push_str("]\n");
+    Ok(index_str)
+}
+
+/// Returns the dates of the channels named `channel_file` archived under `archive_dir` as
+/// `YYYY/MM/DD/<channel_file>`.
+fn archived_dates(archive_dir: &Path, channel_file: &str) -> Result<Vec<String>> {
+    let subdirs = |dir: &Path| -> Result<Vec<(String, PathBuf)>> {
+        let mut subdirs = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                subdirs.push((
+                    entry.file_name().to_string_lossy().into_owned(),
+                    entry.path(),
+                ));
+            }
+        }
+        Ok(subdirs)
+    };
+
+    let mut dates = Vec::new();
+    for (year, year_dir) in subdirs(archive_dir)? {
+        for (month, month_dir) in subdirs(&year_dir)? {
+            for (day, day_dir) in subdirs(&month_dir)? {
+                let date = format!("{year}-{month}-{day}");
+                if day_dir.join(channel_file).is_file()
+                    && chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_ok()
+                {
+                    dates.push(date);
+                }
+            }
+        }
+    }
+    Ok(dates)
+}
+
+/// Signs a channel with the hex-encoded ed25519 seed `signing_key`, returning the hex-encoded
+/// detached signature that fuelup checks the channel against.
+fn sign_channel(signing_key: &str, channel: &str) -> Result<String> {
+    let seed = decode_hex(signing_key.trim());
+    let key_pair = match seed.map(|seed| Ed25519KeyPair::from_seed_unchecked(&seed)) {
+        Some(Ok(key_pair)) => key_pair,
+        _ => bail!("Signing key must be a hex-encoded 32-byte ed25519 seed"),
+    };
+    println!("public key: {}", encode_hex(key_pair.public_key().as_ref()));
+
+    Ok(format!(
+        "{}\n",
+        encode_hex(key_pair.sign(channel.as_bytes()).as_ref())
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sign_channel() {
+        let seed = "07".repeat(32);
+        let channel = "date = \"2023-02-13\"\n";
+        let signature = sign_channel(&seed, channel).unwrap();
+        assert_eq!(signature.trim().len(), 128);
+        assert_eq!(signature, sign_channel(&seed, channel).unwrap());
+        assert!(sign_channel("07", channel).is_err());
+        assert!(sign_channel("not a key", channel).is_err());
+    }
+
+    #[test]
+    fn test_add_to_index() {
+        let index = add_to_index(None, &["2023-02-13".to_string()]).unwrap();
+        assert_eq!(index, "dates = [\n    \"2023-02-13\",\n]\n");
+
+        let dates = ["2023-02-12".to_string(), "2023-02-13".to_string()];
+        let index = add_to_index(Some(&index), &dates).unwrap();
+        assert_eq!(
+            index,
+            "dates = [\n    \"2023-02-12\",\n    \"2023-02-13\",\n]\n"
+        );
+        assert!(add_to_index(Some(&index), &["20230214".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_archived_dates() {
+        let archive = tempfile::tempdir().unwrap();
+        for dir in ["2023/02/13", "2023/02/12", "2023/02/14", "2023/02/xx"] {
+            fs::create_dir_all(archive.path().join(dir)).unwrap();
+        }
+        for dir in ["2023/02/13", "2023/02/12", "2023/02/xx"] {
+            fs::write(archive.path().join(dir).join("channel.toml"), "").unwrap();
+        }
+
+        let mut dates = archived_dates(archive.path(), "channel.toml").unwrap();
+        dates.sort();
+        assert_eq!(dates, vec!["2023-02-12", "2023-02-13"]);
+    }
+
     #[test]
     fn test_get_version_no_broken_prefix() {
         // Test that the version string doesn't have broken prefixes
@@ -64,11 +64,8 @@ fn get_workflow_runs(repo: &str) -> Result<WorkflowRunApiResponse> {
         "{}{}/{}?event=release&status=success",
         GITHUB_API_REPOS_BASE_URL, repo, ACTIONS_RUNS
     );
-    let handle = ureq::builder().user_agent("fuelup").build();
-    let resp = handle
-        .get(&github_actions_runs_api_url)
-        .call()
-        .unwrap_or_else(|_| panic!("Could not get workflow runs for {}", repo));
+    let resp = github::get(&github_actions_runs_api_url)
+        .unwrap_or_else(|e| panic!("Could not get workflow runs for {}: {}", repo, e));
 
     let mut data = Vec::new();
     resp.into_reader().read_to_end(&mut data)?;
@@ -83,8 +80,7 @@ fn get_latest_release_version(repo: &str) -> Result<Version> {
         repo
     );
 
-    let handle = ureq::builder().user_agent("fuelup").build();
-    let response: LatestReleaseApiResponse = match handle.get(&url).call() {
+    let response: LatestReleaseApiResponse = match github::get(&url) {
         Ok(r) => serde_json::from_reader(r.into_reader())?,
         Err(e) => {
             bail!("Could not get latest release for {}: {}", repo, e);
@@ -46,6 +46,8 @@ pub struct Component {
     /// Legacy tarball prefix for versions before the migration cutoff.
     /// Used when the component was bundled differently (e.g., forc-crypto was in forc-binaries).
     pub legacy_tarball_prefix: Option<String>,
+    /// Archive format of release assets: "tar.gz" (the default), "tar.xz", "tar.zst" or "zip".
+    pub archive_format: Option<String>,
 }
 
 impl Component {
@@ -63,6 +65,7 @@ impl Component {
                 legacy_repository_name: None,
                 legacy_before: None,
                 legacy_tarball_prefix: None,
+                archive_format: None,
             });
         }
 
@@ -111,6 +114,11 @@ impl Component {
         &self.tarball_prefix
     }
 
+    /// Returns the file extension of this component's release assets, without the leading dot.
+    pub fn archive_extension(&self) -> &str {
+        self.archive_format.as_deref().unwrap_or("tar.gz")
+    }
+
     /// Returns the git tag format to use for a given component version.
     ///
     /// Different repositories use different tag naming conventions. This method
@@ -364,6 +372,26 @@ impl Components {
     }
 }
 
+/// Decodes a hex string, such as a channel signature or a public key it is checked against.
+pub fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let digit = |c: &u8| {
+        char::from(*c)
+            .to_digit(16)
+            .and_then(|d| u8::try_from(d).ok())
+    };
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => Some(digit(high)? << 4 | digit(low)?),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,6 +426,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_archive_extension() -> Result<()> {
+        const TOML: &str = indoc! {r#"
+            [component.forc-fmt]
+            name = "forc-fmt"
+            tarball_prefix = "forc-binaries"
+            executables = ["forc-fmt"]
+            repository_name = "sway"
+            targets = ["linux_amd64"]
+
+            [component.forc-tool]
+            name = "forc-tool"
+            tarball_prefix = "forc-tool"
+            executables = ["forc-tool"]
+            repository_name = "forc-tool"
+            targets = ["linux_amd64"]
+            archive_format = "zip"
+        "#};
+
+        let components = Components::from_toml(TOML)?;
+
+        assert_eq!(
+            components.component["forc-fmt"].archive_extension(),
+            "tar.gz"
+        );
+        assert_eq!(components.component["forc-tool"].archive_extension(), "zip");
+        Ok(())
+    }
+
     #[test]
     fn test_collect_exclude_plugins() -> Result<()> {
         let components = Components::collect_exclude_plugins().unwrap();
@@ -892,4 +949,13 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(decode_hex("00ff7a"), Some(vec![0x00, 0xff, 0x7a]));
+        assert_eq!(decode_hex("00F"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert_eq!(decode_hex("+f"), None);
+        assert_eq!(encode_hex(&[0x00, 0xff, 0x7a]), "00ff7a");
+    }
 }
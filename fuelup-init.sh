@@ -1,7 +1,7 @@
 #!/bin/sh
 set -e
 
-FUELUP_DIR=${FUELUP_DIR-"$HOME/.fuelup"}
+FUELUP_DIR=${FUELUP_HOME:-${FUELUP_DIR:-"$HOME/.fuelup"}}
 
 main() {
     need_cmd git
@@ -142,7 +142,12 @@ main() {
         if echo "$PATH" | grep -q "$FUELUP_DIR/bin"; then
             printf "\n%s/bin already exists in your PATH.\n" "$FUELUP_DIR"
         else
-            echo "export PATH=\"\$HOME/.fuelup/bin:\$PATH"\" >>"$SHELL_PROFILE"
+            if [ -n "$FUELUP_HOME" ]; then
+                echo "export FUELUP_HOME=\"$FUELUP_DIR\"" >>"$SHELL_PROFILE"
+                echo "export PATH=\"$FUELUP_DIR/bin:\$PATH\"" >>"$SHELL_PROFILE"
+            else
+                echo "export PATH=\"\$HOME/.fuelup/bin:\$PATH"\" >>"$SHELL_PROFILE"
+            fi
             printf "\n%s added to PATH. Run 'source %s' or start a new terminal session to use fuelup.\n" "$FUELUP_DIR" "$SHELL_PROFILE"
         fi
     else
@@ -161,7 +166,7 @@ To use the toolchain, you will have to configure your PATH, which tells your mac
 
 If permitted, fuelup-init will configure your PATH for you by running the following:
 
-    echo "export PATH="\$HOME/.fuelup/bin:\$PATH"" >> $SHELL_PROFILE
+    echo "export PATH="$FUELUP_DIR/bin:\$PATH"" >> $SHELL_PROFILE
 
 Would you like fuelup-init to modify your PATH variable for you? (N/y)
 EOF
@@ -174,11 +179,11 @@ You might have to add $FUELUP_DIR/bin to path:
 
 bash/zsh:
 
-export PATH="\${HOME}/.fuelup/bin:\${PATH}"
+export PATH="$FUELUP_DIR/bin:\${PATH}"
 
 fish:
 
-fish_add_path ~/.fuelup/bin
+fish_add_path $FUELUP_DIR/bin
 EOF
 }
 
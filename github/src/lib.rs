@@ -0,0 +1,113 @@
+//! Requests to the GitHub API, shared by fuelup and the CI tools that publish its channels.
+//!
+//! Unauthenticated requests are limited to 60 per hour per IP address, which shared CI runners
+//! and networks exhaust quickly, so requests are authenticated with a token whenever one is set.
+
+use std::env;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+pub const FUELUP_GITHUB_TOKEN: &str = "FUELUP_GITHUB_TOKEN";
+pub const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
+
+/// The error returned when a request failed because the rate limit was exhausted.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "GitHub API rate limit exceeded, rate limited until {}. Set FUELUP_GITHUB_TOKEN or \
+     GITHUB_TOKEN to raise the limit.",
+    format_reset(.reset)
+)]
+pub struct RateLimited {
+    pub reset: OffsetDateTime,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    RateLimited(#[from] RateLimited),
+    #[error(transparent)]
+    Request(#[from] Box<ureq::Error>),
+}
+
+fn format_reset(reset: &OffsetDateTime) -> String {
+    reset.format(&Rfc3339).unwrap_or_else(|_| reset.to_string())
+}
+
+impl RateLimited {
+    /// Returns the error for `err` if the request failed because the rate limit was exhausted.
+    pub fn from_error(err: &ureq::Error) -> Option<Self> {
+        rate_limit_reset(err).map(|reset| Self { reset })
+    }
+}
+
+/// Returns the token to authenticate requests with, from `FUELUP_GITHUB_TOKEN` or
+/// `GITHUB_TOKEN`.
+pub fn token() -> Option<String> {
+    [FUELUP_GITHUB_TOKEN, GITHUB_TOKEN]
+        .into_iter()
+        .find_map(|key| env::var(key).ok().filter(|token| !token.is_empty()))
+}
+
+/// Builds a GET request to the GitHub API with `agent`, authenticated with [`token`] if it is set.
+pub fn api_request(agent: &ureq::Agent, url: &str) -> ureq::Request {
+    let request = agent.get(url).set("Accept", "application/vnd.github+json");
+    match token() {
+        Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+        None => request,
+    }
+}
+
+/// Sends a GET request to the GitHub API with a default agent.
+pub fn get(url: &str) -> Result<ureq::Response, Error> {
+    let agent = ureq::builder().user_agent("fuelup").build();
+    api_request(&agent, url)
+        .call()
+        .map_err(|err| match RateLimited::from_error(&err) {
+            Some(limited) => limited.into(),
+            None => Box::new(err).into(),
+        })
+}
+
+/// Returns when the rate limit resets, if a request failed because it was exhausted.
+pub fn rate_limit_reset(err: &ureq::Error) -> Option<OffsetDateTime> {
+    let ureq::Error::Status(403 | 429, response) = err else {
+        return None;
+    };
+    if response.header("X-RateLimit-Remaining")?.trim() != "0" {
+        return None;
+    }
+    let reset = response.header("X-RateLimit-Reset")?.trim().parse().ok()?;
+    OffsetDateTime::from_unix_timestamp(reset).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_reset() {
+        let status = |code: u16, headers: &str| {
+            let response = format!("HTTP/1.1 {code} Status\r\n{headers}\r\n");
+            ureq::Error::Status(code, response.parse::<ureq::Response>().unwrap())
+        };
+
+        let limited = status(
+            403,
+            "X-RateLimit-Remaining: 0\r\nX-RateLimit-Reset: 1700000000\r\n",
+        );
+        assert_eq!(
+            rate_limit_reset(&limited).unwrap().unix_timestamp(),
+            1700000000
+        );
+        assert!(RateLimited::from_error(&limited)
+            .unwrap()
+            .to_string()
+            .contains("rate limited until 2023-11-14T22:13:20Z"));
+
+        let forbidden = status(
+            403,
+            "X-RateLimit-Remaining: 42\r\nX-RateLimit-Reset: 1700000000\r\n",
+        );
+        assert!(rate_limit_reset(&forbidden).is_none());
+        assert!(rate_limit_reset(&status(404, "")).is_none());
+    }
+}
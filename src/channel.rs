@@ -1,16 +1,21 @@
 use crate::{
+    channel_cache::download_channel,
     constants::{
         CHANNEL_LATEST_FILE_NAME, CHANNEL_MAINNET_FILE_NAME, CHANNEL_NIGHTLY_FILE_NAME,
-        CHANNEL_TESTNET_FILE_NAME, DATE_FORMAT_URL_FRIENDLY, FUELUP_GH_PAGES,
+        CHANNEL_TESTNET_FILE_NAME, DATE_FORMAT, DATE_FORMAT_URL_FRIENDLY, FUELUP_GH_PAGES,
     },
-    download::{download, DownloadCfg},
+    download::{dist_url, DownloadCfg},
+    file::read_file,
+    path::settings_file,
+    settings::SettingsFile,
+    signature::verify_channel,
     toolchain::{DistToolchainDescription, DistToolchainName},
 };
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use component::Components;
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, fmt::Debug};
+use std::{collections::BTreeMap, fmt::Debug, path::Path};
 use time::Date;
 use toml_edit::de;
 use tracing::warn;
@@ -24,14 +29,39 @@ pub const NIGHTLY: &str = "nightly";
 
 pub const CHANNELS: [&str; 4] = [LATEST, NIGHTLY, TESTNET, MAINNET];
 
+/// The newest channel manifest version this fuelup understands. Manifests without a
+/// `manifest-version` predate versioning and are treated as version 1.
+pub const MANIFEST_VERSION: u32 = 1;
+
+time::serde::format_description!(channel_date, Date, "[year]-[month]-[day]");
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct HashedBinary {
     pub url: String,
     pub hash: String,
+    /// Size of the archive in bytes.
+    pub size: Option<u64>,
+}
+
+/// The dates a channel was published on, as listed in its index on gh-pages.
+#[derive(Debug, Default)]
+pub struct ChannelIndex {
+    /// Publication dates, oldest first.
+    pub dates: Vec<Date>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChannelIndexFile {
+    dates: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Channel {
+    #[serde(rename = "manifest-version")]
+    pub manifest_version: Option<u32>,
+    /// The day the channel was published.
+    #[serde(default, with = "channel_date::option")]
+    pub date: Option<Date>,
     pub pkg: BTreeMap<String, Package>,
 }
 
@@ -40,6 +70,32 @@ pub struct Package {
     pub target: BTreeMap<String, HashedBinary>,
     pub version: Version,
     pub fuels_version: Option<String>,
+    /// Where the release notes of this version can be read.
+    pub release_notes: Option<String>,
+    /// The oldest fuelup able to install this package, eg. because it is published in an archive
+    /// format older versions can't unpack.
+    pub min_fuelup_version: Option<Version>,
+}
+
+impl Package {
+    /// Whether this version of fuelup is new enough to install the package.
+    pub fn is_supported(&self) -> bool {
+        self.min_fuelup_version
+            .as_ref()
+            .is_none_or(|min| fuelup_version() >= *min)
+    }
+}
+
+/// Only the version of a manifest, which is read before the rest of it so that a newer manifest
+/// is reported as such instead of as whatever parse error its changes cause.
+#[derive(Deserialize)]
+struct ManifestHeader {
+    #[serde(rename = "manifest-version")]
+    manifest_version: Option<u32>,
+}
+
+fn fuelup_version() -> Version {
+    Version::parse(env!("CARGO_PKG_VERSION")).expect("fuelup version is valid semver")
 }
 
 pub fn is_dateless_distributed_toolchain(name: &str) -> bool {
@@ -53,9 +109,27 @@ fn format_nightly_url(date: &Date) -> Result<String> {
     ))
 }
 
-fn construct_channel_url(desc: &DistToolchainDescription) -> Result<String> {
-    let mut url = FUELUP_GH_PAGES.to_owned();
-    match desc.name {
+/// Returns the channels registered with `fuelup channel add`, mapping their names to their URLs.
+pub fn custom_channels() -> BTreeMap<String, String> {
+    // Reading settings creates the file if it's missing, which we don't want as a side effect here.
+    let settings_file = settings_file();
+    if !settings_file.exists() {
+        return BTreeMap::new();
+    }
+    SettingsFile::new(settings_file)
+        .with(|s| Ok(s.channels.clone().unwrap_or_default()))
+        .unwrap_or_default()
+}
+
+pub(crate) fn custom_channel_url(name: &str) -> Option<String> {
+    custom_channels().remove(name)
+}
+
+/// Returns the path of a channel relative to the root of the gh-pages branch. Custom channels
+/// aren't published there, but are given a path for the sake of `fuelup mirror`.
+pub fn channel_path(desc: &DistToolchainDescription) -> Result<String> {
+    let mut url = String::new();
+    match &desc.name {
         DistToolchainName::Latest => {
             if let Some(date) = desc.date {
                 url.push_str(&format!("channels/latest/channel-fuel-latest-{date}.toml"));
@@ -73,22 +147,166 @@ fn construct_channel_url(desc: &DistToolchainDescription) -> Result<String> {
         }
         DistToolchainName::Testnet => url.push_str(CHANNEL_TESTNET_FILE_NAME),
         DistToolchainName::Mainnet => url.push_str(CHANNEL_MAINNET_FILE_NAME),
+        DistToolchainName::Custom(name) => {
+            if desc.date.is_some() {
+                bail!("Custom channel '{}' does not have dated versions", name);
+            }
+            url.push_str(&format!("channel-{name}.toml"));
+        }
     };
 
     Ok(url)
 }
 
+/// Returns the path of the index listing the dated versions of a channel, relative to the root of
+/// the gh-pages branch. Only the nightly channel publishes an index of its dated versions.
+pub fn channel_index_path(name: &DistToolchainName) -> Result<String> {
+    match name {
+        DistToolchainName::Nightly => Ok(format!("channels/{name}/index.toml")),
+        _ => bail!(
+            "Channel '{}' does not publish an index of dated versions",
+            name
+        ),
+    }
+}
+
+pub(crate) fn construct_channel_url(desc: &DistToolchainDescription) -> Result<String> {
+    if let DistToolchainName::Custom(name) = &desc.name {
+        channel_path(desc)?;
+        return custom_channel_url(name)
+            .ok_or_else(|| anyhow!("Channel '{}' is not registered", name));
+    }
+    Ok(format!("{FUELUP_GH_PAGES}{}", channel_path(desc)?))
+}
+
+/// Resolves `url` against `base`, the URL of the directory containing a channel. Absolute URLs
+/// are returned unchanged, which allows channels (eg. ones written by `fuelup mirror`) to refer
+/// to tarballs relative to their own location.
+pub(crate) fn resolve_url(base: &str, url: &str) -> String {
+    if url.contains("://") {
+        return url.to_string();
+    }
+
+    let mut resolved = base.strip_suffix('/').unwrap_or(base).to_string();
+    let mut relative = url;
+    loop {
+        if let Some(rest) = relative.strip_prefix("./") {
+            relative = rest;
+        } else if let Some(rest) = relative.strip_prefix("../") {
+            if let Some((parent, _)) = resolved.rsplit_once('/') {
+                if !parent.ends_with('/') {
+                    resolved = parent.to_string();
+                }
+            }
+            relative = rest;
+        } else {
+            break;
+        }
+    }
+    format!("{resolved}/{relative}")
+}
+
+impl ChannelIndex {
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        let index: ChannelIndexFile = de::from_str(toml)?;
+        let mut dates = index
+            .dates
+            .iter()
+            .map(|d| {
+                Date::parse(d, DATE_FORMAT)
+                    .map_err(|_| anyhow!("Invalid date '{}' in channel index", d))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        dates.sort();
+        dates.dedup();
+        Ok(Self { dates })
+    }
+
+    pub fn from_dist(name: &DistToolchainName) -> Result<Self> {
+        let index_url = dist_url(&format!("{FUELUP_GH_PAGES}{}", channel_index_path(name)?));
+        let toml = match download_channel(&index_url) {
+            Ok(t) => t,
+            Err(_) => bail!("Could not read {}", &index_url),
+        };
+        Self::from_toml(&String::from_utf8(toml)?)
+    }
+
+    /// Returns the published date closest to `date`, preferring the earlier one on a tie.
+    pub fn nearest(&self, date: Date) -> Option<Date> {
+        self.dates
+            .iter()
+            .min_by_key(|d| (**d - date).whole_days().abs())
+            .copied()
+    }
+}
+
 impl Channel {
     pub fn from_dist_channel(desc: &DistToolchainDescription) -> Result<Self> {
-        let channel_url = construct_channel_url(desc)?;
-        let toml = match download(&channel_url) {
-            Ok(t) => String::from_utf8(t)?,
+        let channel = Self::from_url(&dist_url(&construct_channel_url(desc)?));
+        match (channel, desc.date) {
+            (Err(e), Some(date)) => {
+                // Point at the closest date that does exist, if the index knows of one.
+                let nearest = ChannelIndex::from_dist(&desc.name)
+                    .ok()
+                    .filter(|index| !index.dates.contains(&date))
+                    .and_then(|index| index.nearest(date));
+                match nearest {
+                    Some(nearest) => bail!(
+                        "{}\nNo {} channel was published on {}, the nearest date available is {}. \
+                        Run `fuelup toolchain list-available {}` to see all dates.",
+                        e,
+                        desc.name,
+                        date,
+                        nearest,
+                        desc.name
+                    ),
+                    None => Err(e),
+                }
+            }
+            (channel, _) => channel,
+        }
+    }
+
+    pub fn from_url(channel_url: &str) -> Result<Self> {
+        let toml = match download_channel(channel_url) {
+            Ok(t) => t,
             Err(_) => bail!("Could not read {}", &channel_url),
         };
-        Self::from_toml(&toml)
+        verify_channel(channel_url, &toml)?;
+        let mut channel = Self::from_toml(&String::from_utf8(toml)?)?;
+        if let Some((base, _)) = channel_url.rsplit_once('/') {
+            channel.resolve_urls(base);
+        }
+        Ok(channel)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let toml = read_file("channel", path)?;
+        let dir = path.canonicalize()?;
+        verify_channel(&format!("file://{}", dir.display()), toml.as_bytes())?;
+        let mut channel = Self::from_toml(&toml)?;
+        if let Some(dir) = dir.parent() {
+            channel.resolve_urls(&format!("file://{}", dir.display()));
+        }
+        Ok(channel)
+    }
+
+    /// Turns relative package URLs into absolute ones, using `base` as the channel's location.
+    fn resolve_urls(&mut self, base: &str) {
+        for binary in self.pkg.values_mut().flat_map(|p| p.target.values_mut()) {
+            binary.url = resolve_url(base, &binary.url);
+        }
     }
 
     pub fn from_toml(toml: &str) -> Result<Self> {
+        let header: ManifestHeader = de::from_str(toml)?;
+        if let Some(version) = header.manifest_version.filter(|v| *v > MANIFEST_VERSION) {
+            bail!(
+                "The channel has manifest version {version}, but this version of fuelup only \
+                 supports up to version {MANIFEST_VERSION}. Run `fuelup self update` to update \
+                 fuelup and try again."
+            );
+        }
         let channel: Channel = de::from_str(toml)?;
         Ok(channel)
     }
@@ -98,6 +316,16 @@ impl Channel {
             .pkg
             .iter()
             .filter(|(component_name, _)| Components::contains_published(component_name))
+            .filter(|(name, package)| match &package.min_fuelup_version {
+                Some(min) if !package.is_supported() => {
+                    warn!(
+                        "Skipping '{}' {}, which requires fuelup {} or newer. Run `fuelup self update` to install it.",
+                        name, package.version, min,
+                    );
+                    false
+                }
+                _ => true,
+            })
             .map(|(name, package)| {
                 DownloadCfg::from_package(name, package).map_err(|_| {
                     warn!(
@@ -117,7 +345,8 @@ If this component should be downloadable, try running `fuelup self update` and r
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{download::DownloadCfg, file::read_file};
+    use crate::download::DownloadCfg;
+    use indoc::indoc;
 
     #[test]
     fn channel_from_toml() {
@@ -211,6 +440,92 @@ mod tests {
         assert!(targets.contains_key("x86_64-unknown-linux-gnu"));
     }
 
+    #[test]
+    fn channel_from_toml_with_metadata() {
+        let channel = Channel::from_toml(indoc! {r#"
+            manifest-version = 1
+            date = "2024-05-01"
+
+            [pkg.fuel-core]
+            version = "0.9.4"
+            release_notes = "https://github.com/FuelLabs/fuel-core/releases/tag/v0.9.4"
+            min_fuelup_version = "0.1.0"
+
+            [pkg.fuel-core.target.x86_64-unknown-linux-gnu]
+            url = "https://github.com/FuelLabs/fuel-core/releases/download/v0.9.4/fuel-core-0.9.4-x86_64-unknown-linux-gnu.tar.gz"
+            hash = "a5a2bedd4cf64e372dae28c435a7902160924424cbc50a6f4b582b5a50134485"
+            size = 12345
+        "#})
+        .unwrap();
+
+        assert_eq!(channel.manifest_version, Some(1));
+        assert_eq!(channel.date, Some(time::macros::date!(2024 - 05 - 01)));
+        let package = &channel.pkg["fuel-core"];
+        assert_eq!(
+            package.release_notes.as_deref(),
+            Some("https://github.com/FuelLabs/fuel-core/releases/tag/v0.9.4")
+        );
+        assert!(package.is_supported());
+        assert_eq!(package.target["x86_64-unknown-linux-gnu"].size, Some(12345));
+    }
+
+    #[test]
+    fn channel_from_toml_newer_manifest_version() {
+        // Newer manifests may change the format in ways this version can't parse at all.
+        let error = Channel::from_toml(indoc! {r#"
+            manifest-version = 2
+
+            [pkg.fuel-core]
+            version = { major = 0, minor = 9 }
+        "#})
+        .unwrap_err();
+
+        assert!(error.to_string().contains("manifest version 2"));
+        assert!(error.to_string().contains("fuelup self update"));
+    }
+
+    #[test]
+    fn download_cfgs_skip_unsupported_packages() {
+        let channel = Channel::from_toml(indoc! {r#"
+            [pkg.fuel-core]
+            version = "0.9.4"
+            min_fuelup_version = "999.0.0"
+
+            [pkg.fuel-core.target.x86_64-unknown-linux-gnu]
+            url = "https://github.com/FuelLabs/fuel-core/releases/download/v0.9.4/fuel-core-0.9.4-x86_64-unknown-linux-gnu.tar.gz"
+            hash = "a5a2bedd4cf64e372dae28c435a7902160924424cbc50a6f4b582b5a50134485"
+        "#})
+        .unwrap();
+
+        assert!(!channel.pkg["fuel-core"].is_supported());
+        assert!(channel.build_download_configs().is_empty());
+    }
+
+    #[test]
+    fn resolve_relative_urls() {
+        let base = "https://mirror.example.com/fuel/channels/nightly/2024/05/01";
+        assert_eq!(
+            resolve_url(base, "https://github.com/FuelLabs/forc.tar.gz"),
+            "https://github.com/FuelLabs/forc.tar.gz"
+        );
+        assert_eq!(
+            resolve_url(base, "forc.tar.gz"),
+            "https://mirror.example.com/fuel/channels/nightly/2024/05/01/forc.tar.gz"
+        );
+        assert_eq!(
+            resolve_url(base, "../../../../../sway/releases/forc.tar.gz"),
+            "https://mirror.example.com/fuel/sway/releases/forc.tar.gz"
+        );
+        assert_eq!(
+            resolve_url("file:///srv/mirror/", "./sway/forc.tar.gz"),
+            "file:///srv/mirror/sway/forc.tar.gz"
+        );
+        assert_eq!(
+            resolve_url("file:///", "../forc.tar.gz"),
+            "file:///forc.tar.gz"
+        );
+    }
+
     #[test]
     fn download_cfgs_from_channel() {
         let channel_path = std::env::current_dir()
@@ -227,4 +542,35 @@ mod tests {
         assert_eq!(cfgs[1].name, "fuel-core");
         assert_eq!(cfgs[1].version, Version::parse("0.9.4").unwrap());
     }
+
+    #[test]
+    fn channel_index_from_toml() {
+        let index = ChannelIndex::from_toml(indoc! {r#"
+            dates = ["2024-05-03", "2024-05-01", "2024-05-10", "2024-05-01"]
+        "#})
+        .unwrap();
+        let date = |d| Date::parse(d, DATE_FORMAT).unwrap();
+
+        assert_eq!(
+            index.dates,
+            vec![date("2024-05-01"), date("2024-05-03"), date("2024-05-10")]
+        );
+        assert_eq!(index.nearest(date("2024-05-02")), Some(date("2024-05-01")));
+        assert_eq!(index.nearest(date("2024-05-08")), Some(date("2024-05-10")));
+        assert_eq!(index.nearest(date("2025-01-01")), Some(date("2024-05-10")));
+        assert!(ChannelIndex::default()
+            .nearest(date("2024-05-01"))
+            .is_none());
+        assert!(ChannelIndex::from_toml(r#"dates = ["2024-13-01"]"#).is_err());
+    }
+
+    #[test]
+    fn channel_index_path_only_for_nightly() {
+        assert_eq!(
+            channel_index_path(&DistToolchainName::Nightly).unwrap(),
+            "channels/nightly/index.toml"
+        );
+        assert!(channel_index_path(&DistToolchainName::Latest).is_err());
+        assert!(channel_index_path(&DistToolchainName::Mainnet).is_err());
+    }
 }
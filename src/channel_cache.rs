@@ -0,0 +1,254 @@
+//! Caching of fetched channels and their signatures, revalidated with the `ETag` and
+//! `Last-Modified` headers they were served with.
+
+use crate::{
+    constants::{FUELUP_CHANNEL_TTL, FUELUP_OFFLINE},
+    download::{build_agent, download, local_path, setting, RetryPolicy},
+    path::{channel_cache_dir, ensure_dir_exists},
+};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, warn};
+
+/// How fetched channels are reused instead of being downloaded again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ChannelCachePolicy {
+    /// How long a cached channel is used for before checking whether it has changed.
+    pub(crate) ttl: Duration,
+    /// Whether to only use cached channels, failing if there are none.
+    pub(crate) offline: bool,
+}
+
+impl ChannelCachePolicy {
+    /// Reads the policy from `FUELUP_CHANNEL_TTL` and `FUELUP_OFFLINE`, falling back to the
+    /// `channel_ttl_secs` and `offline` keys in settings.toml.
+    pub(crate) fn from_env() -> Self {
+        Self {
+            ttl: setting(FUELUP_CHANNEL_TTL, |s| s.channel_ttl_secs)
+                .map_or(Duration::ZERO, Duration::from_secs),
+            offline: setting(FUELUP_OFFLINE, |s| s.offline).unwrap_or(false),
+        }
+    }
+}
+
+/// What is kept next to a cached channel to tell whether it is still current.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// The SHA-256 digest of the cached body, so that a body is never paired with the validators
+    /// of another response.
+    sha256: String,
+    /// When the response was last fetched or revalidated, in seconds since the Unix epoch.
+    fetched_at: u64,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Returns the paths of the body and the metadata of the cached response for `url`.
+fn cached_response_paths(cache_dir: &Path, url: &str) -> (PathBuf, PathBuf) {
+    let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+    (cache_dir.join(&key), cache_dir.join(format!("{key}.json")))
+}
+
+fn read_cached_response(url: &str, body: &Path, meta: &Path) -> Option<(CachedResponse, Vec<u8>)> {
+    let cached: CachedResponse = serde_json::from_slice(&fs::read(meta).ok()?).ok()?;
+    if cached.url != url {
+        return None;
+    }
+    let body = fs::read(body).ok()?;
+    if format!("{:x}", Sha256::digest(&body)) != cached.sha256 {
+        debug!(
+            "Discarding the cached copy of {} as it does not match its metadata",
+            url
+        );
+        return None;
+    }
+    Some((cached, body))
+}
+
+/// Writes `contents` to `path` in the cache, through a temporary file so that concurrent fuelup
+/// processes never read a partial entry.
+fn write_cache_entry(cache_dir: &Path, path: &Path, contents: &[u8]) -> Result<()> {
+    ensure_dir_exists(cache_dir)?;
+    let tmp = tempfile::NamedTempFile::new_in(cache_dir)?;
+    fs::write(tmp.path(), contents)?;
+    tmp.persist(path)?;
+    Ok(())
+}
+
+/// Downloads a channel, or a file published alongside one, reusing the copy fetched last time
+/// when the server reports that it hasn't changed.
+pub fn download_channel(url: &str) -> Result<Vec<u8>> {
+    if local_path(url).is_some() {
+        return download(url);
+    }
+    download_cached(
+        url,
+        &channel_cache_dir(),
+        ChannelCachePolicy::from_env(),
+        &RetryPolicy::from_env(),
+    )
+}
+
+fn download_cached(
+    url: &str,
+    cache_dir: &Path,
+    cache: ChannelCachePolicy,
+    retry: &RetryPolicy,
+) -> Result<Vec<u8>> {
+    let (body_path, meta_path) = cached_response_paths(cache_dir, url);
+    let cached = read_cached_response(url, &body_path, &meta_path);
+
+    if cache.offline {
+        return match cached {
+            Some((_, body)) => Ok(body),
+            None => bail!("{} has not been cached and cannot be fetched offline", url),
+        };
+    }
+    if let Some((meta, body)) = &cached {
+        if unix_now().saturating_sub(meta.fetched_at) < cache.ttl.as_secs() {
+            debug!("Using cached {}", url);
+            return Ok(body.clone());
+        }
+    }
+
+    let handle = build_agent(url)?;
+    let response = retry.call(url, || {
+        let mut request = handle.get(url);
+        if let Some((meta, _)) = &cached {
+            if let Some(etag) = &meta.etag {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &meta.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+        request
+    })?;
+
+    if let (304, Some((mut meta, body))) = (response.status(), cached) {
+        debug!("{} has not changed, using the cached copy", url);
+        meta.fetched_at = unix_now();
+        if let Err(e) = write_cache_entry(cache_dir, &meta_path, &serde_json::to_vec(&meta)?) {
+            warn!("Failed to update the cache of {}: {}", url, e);
+        }
+        return Ok(body);
+    }
+
+    let etag = response.header("ETag").map(str::to_string);
+    let last_modified = response.header("Last-Modified").map(str::to_string);
+    let mut body = Vec::new();
+    response.into_reader().read_to_end(&mut body)?;
+    let meta = CachedResponse {
+        url: url.to_string(),
+        etag,
+        last_modified,
+        sha256: format!("{:x}", Sha256::digest(&body)),
+        fetched_at: unix_now(),
+    };
+
+    // The metadata records the digest of the body it belongs to, so if another process or an
+    // interrupted write leaves the two out of step, the entry is discarded rather than revalidated
+    // with the wrong ETag.
+    let written = write_cache_entry(cache_dir, &body_path, &body)
+        .and_then(|_| write_cache_entry(cache_dir, &meta_path, &serde_json::to_vec(&meta)?));
+    if let Err(e) = written {
+        warn!("Failed to cache {}: {}", url, e);
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Write, net::TcpListener, thread};
+
+    #[test]
+    fn test_download_cached_revalidates() -> anyhow::Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/channel.toml", listener.local_addr()?);
+
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in [
+                "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 4\r\nConnection: close\r\n\r\nfuel",
+                "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n",
+            ] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0; 1024];
+                let n = stream.read(&mut request).unwrap();
+                requests.push(String::from_utf8_lossy(&request[..n]).to_lowercase());
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        let dir = tempfile::tempdir()?;
+        let policy = RetryPolicy {
+            attempts: 1,
+            ..Default::default()
+        };
+        let cache = ChannelCachePolicy::default();
+        assert_eq!(download_cached(&url, dir.path(), cache, &policy)?, b"fuel");
+        assert_eq!(download_cached(&url, dir.path(), cache, &policy)?, b"fuel");
+
+        let requests = server.join().unwrap();
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_cached_ttl_and_offline() -> anyhow::Result<()> {
+        // Nothing listens on this port, so any request that is made fails.
+        let url = "http://127.0.0.1:9/channel.toml";
+        let dir = tempfile::tempdir()?;
+        let policy = RetryPolicy {
+            attempts: 1,
+            ..Default::default()
+        };
+        let offline = ChannelCachePolicy {
+            offline: true,
+            ..Default::default()
+        };
+        assert!(download_cached(url, dir.path(), offline, &policy).is_err());
+
+        let (body, meta) = cached_response_paths(dir.path(), url);
+        fs::write(&body, "fuel")?;
+        let cached = CachedResponse {
+            url: url.to_string(),
+            etag: None,
+            last_modified: None,
+            sha256: format!("{:x}", Sha256::digest(b"fuel")),
+            fetched_at: unix_now(),
+        };
+        fs::write(&meta, serde_json::to_vec(&cached)?)?;
+
+        assert_eq!(download_cached(url, dir.path(), offline, &policy)?, b"fuel");
+        let ttl = ChannelCachePolicy {
+            ttl: Duration::from_secs(60),
+            ..Default::default()
+        };
+        assert_eq!(download_cached(url, dir.path(), ttl, &policy)?, b"fuel");
+        let expired = ChannelCachePolicy::default();
+        assert!(download_cached(url, dir.path(), expired, &policy).is_err());
+
+        // A body left behind by another response is not used with this metadata.
+        fs::write(&body, "fuel v2")?;
+        assert!(download_cached(url, dir.path(), offline, &policy).is_err());
+        Ok(())
+    }
+}
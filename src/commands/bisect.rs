@@ -0,0 +1,22 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::ops::fuelup_bisect;
+
+#[derive(Debug, Parser)]
+pub struct BisectCommand {
+    /// Dated toolchain the command is known to succeed with, eg. `nightly-2024-04-01`
+    #[clap(long)]
+    pub good: String,
+    /// Dated toolchain the command is known to fail with, eg. `nightly-2024-05-01`
+    #[clap(long)]
+    pub bad: String,
+    /// Command to run with each toolchain. A toolchain is bad if the command exits with a
+    /// non-zero status.
+    #[clap(last = true, required = true)]
+    pub command: Vec<String>,
+}
+
+pub fn exec(command: BisectCommand) -> Result<()> {
+    fuelup_bisect::bisect(command)
+}
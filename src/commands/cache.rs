@@ -0,0 +1,16 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::ops::fuelup_cache;
+
+#[derive(Debug, Parser)]
+pub enum CacheCommand {
+    /// Remove all cached downloads and channels
+    Clean,
+}
+
+pub fn exec(command: CacheCommand) -> Result<()> {
+    match command {
+        CacheCommand::Clean => fuelup_cache::clean(),
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::ops::fuelup_channel::{add, diff, list, remove};
+
+#[derive(Debug, Parser)]
+pub enum ChannelCommand {
+    /// Register a channel so that it can be installed like a distributable toolchain
+    Add(AddCommand),
+    /// Unregister a channel
+    Remove(RemoveCommand),
+    /// List registered channels
+    List,
+    /// Show how package versions differ between two distributable toolchains
+    Diff(DiffCommand),
+}
+
+#[derive(Debug, Parser)]
+pub struct AddCommand {
+    /// Name to install the channel's toolchain by, eg. `fuelup toolchain install <NAME>`
+    pub name: String,
+    /// URL or path of the channel TOML
+    pub url: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct RemoveCommand {
+    /// Name of the channel to unregister
+    pub name: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct DiffCommand {
+    /// Toolchain to compare from, eg. `nightly-2024-04-01`
+    pub from: String,
+    /// Toolchain to compare to, eg. `nightly-2024-05-01`
+    pub to: String,
+    /// Print the differences as JSON
+    #[clap(long)]
+    pub json: bool,
+}
+
+pub fn exec(command: ChannelCommand) -> Result<()> {
+    match command {
+        ChannelCommand::Add(command) => add(command),
+        ChannelCommand::Remove(command) => remove(command),
+        ChannelCommand::List => list(),
+        ChannelCommand::Diff(command) => diff(command),
+    }
+}
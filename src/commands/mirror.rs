@@ -0,0 +1,18 @@
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+use crate::ops::fuelup_mirror;
+
+#[derive(Debug, Parser)]
+pub struct MirrorCommand {
+    /// Channel to mirror, eg. latest, nightly-2024-01-15 or mainnet
+    pub channel: String,
+    /// Directory to write the channel and its artifacts to. The directory can be served as-is
+    /// and used as a `FUELUP_DIST_SERVER`.
+    pub dir: PathBuf,
+}
+
+pub fn exec(command: MirrorCommand) -> Result<()> {
+    fuelup_mirror::mirror(command)
+}
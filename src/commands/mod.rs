@@ -1,7 +1,11 @@
+pub mod bisect;
+pub mod cache;
+pub mod channel;
 pub mod check;
 pub mod completions;
 pub mod component;
 pub mod default;
 pub mod fuelup;
+pub mod mirror;
 pub mod toolchain;
 pub mod upgrade;
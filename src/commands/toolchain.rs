@@ -1,15 +1,24 @@
+use crate::channel::custom_channel_url;
+use crate::constants::DATE_FORMAT;
 use crate::ops::fuelup_toolchain::{
-    export::export, install::install, new::new, uninstall::uninstall,
+    export::export, install::install, list::list, list_available::list_available, new::new,
+    uninstall::uninstall,
 };
 use crate::target_triple::TargetTriple;
 use crate::toolchain::RESERVED_TOOLCHAIN_NAMES;
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use clap::Parser;
+use std::path::PathBuf;
+use time::Date;
 
 #[derive(Debug, Parser)]
 pub enum ToolchainCommand {
     /// Install or update a distributable toolchain
     Install(InstallCommand),
+    /// List installed toolchains with their components, size and status
+    List(ListCommand),
+    /// List the dated versions of a channel that can be installed
+    ListAvailable(ListAvailableCommand),
     /// Create a new custom toolchain
     New(NewCommand),
     /// Uninstall a toolchain
@@ -20,8 +29,37 @@ pub enum ToolchainCommand {
 
 #[derive(Debug, Parser)]
 pub struct InstallCommand {
-    /// Toolchain name [possible values: latest, nightly, testnet, mainnet]
+    /// Toolchain name [possible values: latest, nightly, testnet, mainnet], or the path or URL of
+    /// a channel TOML to install as a custom toolchain named by --name
     pub name: String,
+    /// Name of the toolchain to install a channel file or URL as
+    #[clap(long = "name", value_parser = name_allowed)]
+    pub toolchain_name: Option<String>,
+    /// Read the channel from a local file instead of fetching it
+    #[clap(long)]
+    pub channel_file: Option<PathBuf>,
+    /// Directory containing the tarballs listed in the channel, matched by file name
+    #[clap(long)]
+    pub artifacts: Option<PathBuf>,
+    /// Install without network access, using only --channel-file and --artifacts
+    #[clap(long, requires_all = ["channel_file", "artifacts"])]
+    pub offline: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct ListCommand {
+    /// Print the toolchains as JSON
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct ListAvailableCommand {
+    /// Channel to list the dated versions of [possible values: nightly]
+    pub channel: String,
+    /// Only list versions published on or after this date (YYYY-MM-DD)
+    #[clap(long, value_parser = parse_date)]
+    pub since: Option<Date>,
 }
 
 #[derive(Debug, Parser)]
@@ -49,6 +87,10 @@ pub struct ExportCommand {
     pub force: bool,
 }
 
+fn parse_date(s: &str) -> Result<Date> {
+    Date::parse(s, DATE_FORMAT).map_err(|_| anyhow!("Invalid date '{}', expected YYYY-MM-DD", s))
+}
+
 fn name_allowed(s: &str) -> Result<String> {
     let name = match s.split_once('-') {
         Some((prefix, target_triple)) => {
@@ -66,6 +108,11 @@ fn name_allowed(s: &str) -> Result<String> {
             "Cannot use distributable toolchain name '{}' as a custom toolchain name",
             s
         )
+    } else if custom_channel_url(name).is_some() {
+        bail!(
+            "Cannot use the name of the registered channel '{}' as a custom toolchain name",
+            name
+        )
     } else {
         Ok(s.to_string())
     }
@@ -74,6 +121,8 @@ fn name_allowed(s: &str) -> Result<String> {
 pub fn exec(command: ToolchainCommand) -> Result<()> {
     match command {
         ToolchainCommand::Install(command) => install(command)?,
+        ToolchainCommand::List(command) => list(command)?,
+        ToolchainCommand::ListAvailable(command) => list_available(command)?,
         ToolchainCommand::New(command) => new(command)?,
         ToolchainCommand::Uninstall(command) => uninstall(command)?,
         ToolchainCommand::Export(command) => export(command)?,
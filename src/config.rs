@@ -1,3 +1,4 @@
+use crate::channel::custom_channels;
 use crate::fmt::format_toolchain_with_target;
 use crate::path::toolchains_dir;
 use crate::toolchain::RESERVED_TOOLCHAIN_NAMES;
@@ -54,7 +55,12 @@ impl Config {
                 .map(|e| e.file_name().into_string().ok().unwrap_or_default())
                 .collect();
 
-            for name in RESERVED_TOOLCHAIN_NAMES {
+            let custom_channels = custom_channels();
+            let names = RESERVED_TOOLCHAIN_NAMES
+                .iter()
+                .copied()
+                .chain(custom_channels.keys().map(String::as_str));
+            for name in names {
                 let dist_toolchain = format_toolchain_with_target(name);
                 if installed_toolchains.contains(&dist_toolchain) {
                     dist_toolchains.push(name.to_string());
@@ -1,11 +1,27 @@
 use time::{format_description::FormatItem, macros::format_description};
 
 pub const FUELUP_GH_PAGES: &str = "https://raw.githubusercontent.com/FuelLabs/fuelup/gh-pages/";
+pub const FUELLABS_GITHUB_URL: &str = "https://github.com/FuelLabs/";
+pub const FUELUP_DIST_SERVER: &str = "FUELUP_DIST_SERVER";
+pub const FUELUP_CHANNEL_PUBLIC_KEYS: &str = "FUELUP_CHANNEL_PUBLIC_KEYS";
+pub const FUELUP_REQUIRE_SIGNED_CHANNELS: &str = "FUELUP_REQUIRE_SIGNED_CHANNELS";
+pub const FUELUP_CA_BUNDLE: &str = "FUELUP_CA_BUNDLE";
+pub const SSL_CERT_FILE: &str = "SSL_CERT_FILE";
+pub const FUELUP_CONNECT_TIMEOUT: &str = "FUELUP_CONNECT_TIMEOUT";
+pub const FUELUP_READ_TIMEOUT: &str = "FUELUP_READ_TIMEOUT";
+pub const FUELUP_RETRY_ATTEMPTS: &str = "FUELUP_RETRY_ATTEMPTS";
+pub const FUELUP_RETRY_DELAY_MS: &str = "FUELUP_RETRY_DELAY_MS";
+pub const FUELUP_DOWNLOAD_CACHE: &str = "FUELUP_DOWNLOAD_CACHE";
+pub const FUELUP_CHANNEL_TTL: &str = "FUELUP_CHANNEL_TTL";
+pub const FUELUP_OFFLINE: &str = "FUELUP_OFFLINE";
 pub const FUEL_TOOLCHAIN_TOML_FILE: &str = "fuel-toolchain.toml";
 pub const FUELS_VERSION_FILE: &str = "fuels_version";
+pub const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+/// Upper bound on the total size of the files unpacked from a single archive.
+pub const MAX_UNPACKED_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_READ_TIMEOUT_SECS: u64 = 30;
 
-pub const CHANNEL_LATEST_URL: &str =
-    "https://raw.githubusercontent.com/FuelLabs/fuelup/gh-pages/channel-fuel-testnet.toml";
 pub const CHANNEL_NIGHTLY_FILE_NAME: &str = "channel-fuel-nightly.toml";
 pub const CHANNEL_TESTNET_FILE_NAME: &str = "channel-fuel-testnet.toml";
 pub const CHANNEL_MAINNET_FILE_NAME: &str = "channel-fuel-mainnet.toml";
@@ -1,34 +1,109 @@
 use crate::{
-    channel::{Channel, Package},
-    constants::CHANNEL_LATEST_URL,
+    channel::Package,
+    constants::{
+        DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_READ_TIMEOUT_SECS, FUELLABS_GITHUB_URL,
+        FUELUP_CA_BUNDLE, FUELUP_CONNECT_TIMEOUT, FUELUP_DIST_SERVER, FUELUP_DOWNLOAD_CACHE,
+        FUELUP_GH_PAGES, FUELUP_READ_TIMEOUT, FUELUP_RETRY_ATTEMPTS, FUELUP_RETRY_DELAY_MS,
+        MAX_UNPACKED_SIZE, SSL_CERT_FILE,
+    },
+    path::{downloads_dir, ensure_dir_exists, fuelup_tmp_dir, settings_file},
+    settings::{Settings, SettingsFile},
     target_triple::TargetTriple,
-    toolchain::DistToolchainDescription,
+    versions::LatestVersions,
 };
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use component::{Component, FUELUP};
 use flate2::read::GzDecoder;
-use indicatif::{FormattedDuration, HumanBytes, HumanDuration, ProgressBar, ProgressStyle};
+use indicatif::{
+    FormattedDuration, HumanBytes, HumanDuration, MultiProgress, ProgressBar, ProgressStyle,
+};
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
+    cell::Cell,
     env,
     fs::{self, File, OpenOptions},
-    io::{Read, Write},
-    path::{Path, PathBuf},
+    io::{self, BufReader, Read, Write},
+    path::{Component as PathComponent, Path, PathBuf},
     str::FromStr,
+    sync::{Arc, OnceLock},
     thread,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tar::Archive;
+use time::OffsetDateTime;
 use tracing::{debug, error, info, warn};
 use ureq::Response;
+use xz2::read::XzDecoder;
+use zip::ZipArchive;
 
 fn github_releases_download_url(repo: &str, tag: &Version, tarball: &str) -> String {
-    format!("https://github.com/FuelLabs/{repo}/releases/download/v{tag}/{tarball}")
+    format!("{FUELLABS_GITHUB_URL}{repo}/releases/download/v{tag}/{tarball}")
 }
 
 fn github_releases_download_url_with_tag(repo: &str, tag: &str, tarball: &str) -> String {
-    format!("https://github.com/FuelLabs/{repo}/releases/download/{tag}/{tarball}")
+    format!("{FUELLABS_GITHUB_URL}{repo}/releases/download/{tag}/{tarball}")
+}
+
+/// Reads a setting from the environment variable `key`, falling back to settings.toml if the
+/// variable is unset or cannot be parsed.
+pub(crate) fn setting<T: FromStr>(key: &str, f: impl FnOnce(&Settings) -> Option<T>) -> Option<T> {
+    if let Some(value) = env::var(key)
+        .ok()
+        .filter(|s| !s.is_empty())
+        .and_then(|s| s.parse().ok())
+    {
+        return Some(value);
+    }
+
+    // Reading settings creates the file if it's missing, which we don't want as a side effect here.
+    let settings_file = settings_file();
+    if settings_file.exists() {
+        SettingsFile::new(settings_file)
+            .with(|s| Ok(f(s)))
+            .ok()
+            .flatten()
+    } else {
+        None
+    }
+}
+
+/// Returns the configured distribution server, if any. `FUELUP_DIST_SERVER` takes precedence over
+/// the `dist_server` key in settings.toml.
+pub fn dist_server() -> Option<String> {
+    setting(FUELUP_DIST_SERVER, |s| s.dist_server.clone())
+}
+
+/// Rewrites channel and release artifact URLs to point at the configured distribution server.
+/// URLs are returned unchanged if no distribution server is configured.
+pub fn dist_url(url: &str) -> String {
+    match dist_server() {
+        Some(server) => rewrite_dist_url(url, &server),
+        None => url.to_string(),
+    }
+}
+
+/// Replaces the gh-pages and GitHub releases prefixes of `url` with `server`, so that a mirror
+/// is expected to serve:
+///
+///   <server>/channel-fuel-mainnet.toml
+///   <server>/channels/nightly/<YYYY>/<MM>/<DD>/channel-fuel-nightly.toml
+///   <server>/channels/nightly/index.toml
+///   <server>/<repo>/releases/download/<tag>/<tarball>
+fn rewrite_dist_url(url: &str, server: &str) -> String {
+    let server = server.trim_end_matches('/');
+    for upstream in [FUELUP_GH_PAGES, FUELLABS_GITHUB_URL] {
+        if let Some(path) = url.strip_prefix(upstream) {
+            return format!("{server}/{path}");
+        }
+    }
+    url.to_string()
+}
+
+/// Returns the local path of a `file://` URL.
+pub(crate) fn local_path(url: &str) -> Option<PathBuf> {
+    url.strip_prefix("file://").map(PathBuf::from)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,6 +113,41 @@ struct LatestReleaseApiResponse {
     name: String,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum DownloadError {
+    #[error("Checksum mismatch for {tarball_name}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        tarball_name: String,
+        expected: String,
+        actual: String,
+    },
+    #[error(transparent)]
+    RateLimited(#[from] github::RateLimited),
+}
+
+/// Errors unpacking a downloaded archive. Extraction stops at the first unsafe or corrupt entry,
+/// leaving whatever was already unpacked for the caller to clean up.
+#[derive(Debug, thiserror::Error)]
+pub enum ExtractError {
+    #[error(
+        "Refusing to unpack '{}' as it would be written outside of the destination directory",
+        path.display()
+    )]
+    UnsafePath { path: PathBuf },
+    #[error(
+        "Refusing to unpack '{}' as it links to '{}' outside of the destination directory",
+        path.display(),
+        target.display()
+    )]
+    UnsafeLink { path: PathBuf, target: PathBuf },
+    #[error("Archive unpacks to more than {}", HumanBytes(*limit))]
+    TooLarge { limit: u64 },
+    #[error("Failed to unpack archive: {0}. The archive could be corrupted or the release may not be ready yet")]
+    Corrupted(#[from] io::Error),
+    #[error("Failed to remove '{}' after unpacking it: {source}", path.display())]
+    RemoveArchive { path: PathBuf, source: io::Error },
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct DownloadCfg {
     pub name: String,
@@ -46,6 +156,7 @@ pub struct DownloadCfg {
     tarball_name: String,
     tarball_url: String,
     hash: Option<String>,
+    fuels_version: Option<String>,
 }
 
 impl DownloadCfg {
@@ -56,15 +167,24 @@ impl DownloadCfg {
                 .map_err(|e| anyhow!("Error getting latest tag for '{}': {}", name, e))?,
         };
         let (tarball_name, tarball_url) = if name == FUELUP {
-            let tarball_name = tarball_name(FUELUP, &version, &target);
-            let tarball_url = github_releases_download_url(FUELUP, &version, &tarball_name);
+            let tarball_name = tarball_name(FUELUP, &version, &target, ArchiveFormat::TarGz);
+            let tarball_url = dist_url(&github_releases_download_url(
+                FUELUP,
+                &version,
+                &tarball_name,
+            ));
             (tarball_name, tarball_url)
         } else if let Ok(component) = Component::from_name(name) {
             let tarball_prefix = component.tarball_prefix_for_version(&version);
-            let tarball_name = tarball_name(tarball_prefix, &version, &target);
+            let format = component.archive_extension().parse()?;
+            let tarball_name = tarball_name(tarball_prefix, &version, &target, format);
             let repo = component.repository_for_version(&version);
             let tag = component.tag_for_version(&version);
-            let tarball_url = github_releases_download_url_with_tag(repo, &tag, &tarball_name);
+            let tarball_url = dist_url(&github_releases_download_url_with_tag(
+                repo,
+                &tag,
+                &tarball_name,
+            ));
             (tarball_name, tarball_url)
         } else {
             bail!("Unrecognized component: {}", name)
@@ -77,43 +197,264 @@ impl DownloadCfg {
             tarball_name,
             tarball_url,
             hash: None,
+            fuels_version: None,
         })
     }
 
     pub fn from_package(name: &str, package: &Package) -> Result<Self> {
         let target = TargetTriple::from_component(name)?;
-        let tarball_name = tarball_name(name, &package.version, &target);
         let hashed_binary = package
             .target
             .get(&target.to_string())
             .ok_or_else(|| anyhow!("No binary for target: {}", target))?;
+        let format = ArchiveFormat::from_file_name(&hashed_binary.url).unwrap_or_default();
+        let tarball_name = tarball_name(name, &package.version, &target, format);
 
         Ok(Self {
             name: name.to_string(),
             target,
             version: package.version.clone(),
             tarball_name,
-            tarball_url: hashed_binary.url.clone(),
+            tarball_url: dist_url(&hashed_binary.url),
             hash: Some(hashed_binary.hash.clone()),
+            fuels_version: package.fuels_version.clone(),
         })
     }
+
+    /// Resolves the tarball from a local directory of artifacts instead of its URL. Tarballs are
+    /// matched by the file name in the URL, so `dir` can be populated by downloading the URLs
+    /// listed in a channel.
+    pub fn with_artifacts_dir(mut self, dir: &Path) -> Self {
+        let file_name = self
+            .tarball_url
+            .rsplit('/')
+            .next()
+            .unwrap_or(&self.tarball_name);
+        self.tarball_url = format!("file://{}", dir.join(file_name).display());
+        self
+    }
+
+    /// Whether the tarball is read from the local filesystem rather than downloaded.
+    pub(crate) fn is_local(&self) -> bool {
+        self.local_tarball().is_some()
+    }
+
+    /// The path the tarball is read from, if it isn't downloaded.
+    pub(crate) fn local_tarball(&self) -> Option<PathBuf> {
+        local_path(&self.tarball_url)
+    }
+
+    /// The version of the `fuels` dependency declared by the channel, if any.
+    pub(crate) fn fuels_version(&self) -> Option<&str> {
+        self.fuels_version.as_deref()
+    }
+}
+
+/// How failed HTTP requests are retried. A request is made up to `attempts` times, waiting with
+/// jittered exponential backoff between attempts unless the server asks for a specific delay
+/// through `Retry-After`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RetryPolicy {
+    pub(crate) attempts: u32,
+    pub(crate) base_delay: Duration,
+    pub(crate) max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 4,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The longest we are willing to wait when a server asks us to retry later.
+    const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+    /// Reads the retry policy from `FUELUP_RETRY_ATTEMPTS` and `FUELUP_RETRY_DELAY_MS`, falling
+    /// back to the `retry_attempts` and `retry_delay_ms` keys in settings.toml.
+    pub(crate) fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            attempts: setting(FUELUP_RETRY_ATTEMPTS, |s| s.retry_attempts)
+                .unwrap_or(default.attempts)
+                .max(1),
+            base_delay: setting(FUELUP_RETRY_DELAY_MS, |s| s.retry_delay_ms)
+                .map_or(default.base_delay, Duration::from_millis),
+            ..default
+        }
+    }
+
+    /// Returns how long to wait after the given failed attempt, counting from 1.
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(Self::MAX_RETRY_AFTER);
+        }
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        // Jitter keeps concurrent downloads from retrying in lockstep.
+        backoff / 2 + jitter(backoff / 2)
+    }
+
+    /// Sleeps before the next attempt, returning false if there are no attempts left.
+    fn backoff(&self, attempt: u32, retry_after: Option<Duration>) -> bool {
+        if attempt >= self.attempts {
+            return false;
+        }
+        let delay = self.delay(attempt, retry_after);
+        info!(
+            "Retrying in {} (attempt {}/{})..",
+            HumanDuration(delay),
+            attempt + 1,
+            self.attempts
+        );
+        thread::sleep(delay);
+        true
+    }
+
+    /// Makes the request built by `request` until it succeeds, fails with an error that is not
+    /// transient, or runs out of attempts.
+    pub(crate) fn call(&self, url: &str, request: impl Fn() -> ureq::Request) -> Result<Response> {
+        self.call_retrying(url, request, is_transient)
+    }
+
+    /// Like [`RetryPolicy::call`], but retries the errors for which `retryable` returns true
+    /// instead of only transient ones. Rate limits are handled the same either way.
+    pub(crate) fn call_retrying(
+        &self,
+        url: &str,
+        request: impl Fn() -> ureq::Request,
+        retryable: impl Fn(&ureq::Error) -> bool,
+    ) -> Result<Response> {
+        let mut attempt = 1;
+        loop {
+            let err = match request().call() {
+                Ok(response) => return Ok(response),
+                Err(e) => e,
+            };
+            let retry_after = match github::rate_limit_reset(&err) {
+                // Wait out rate limits that reset soon, and give up on the others right away.
+                Some(reset) => match Duration::try_from(reset - OffsetDateTime::now_utc()) {
+                    Ok(wait) if wait > Self::MAX_RETRY_AFTER => {
+                        return Err(request_error(url, err))
+                    }
+                    wait => Some(wait.unwrap_or_default()),
+                },
+                None if retryable(&err) => retry_after(&err),
+                None => return Err(request_error(url, err)),
+            };
+            warn!("Failed to download from {}: {}", url, err);
+            if !self.backoff(attempt, retry_after) {
+                return Err(request_error(url, err));
+            }
+            attempt += 1;
+        }
+    }
+}
+
+/// Whether a failed request may succeed if it is made again.
+fn is_transient(err: &ureq::Error) -> bool {
+    match err {
+        ureq::Error::Status(code, _) => matches!(code, 408 | 429 | 500..=599),
+        ureq::Error::Transport(t) => matches!(
+            t.kind(),
+            ureq::ErrorKind::Dns
+                | ureq::ErrorKind::ConnectionFailed
+                | ureq::ErrorKind::Io
+                | ureq::ErrorKind::ProxyConnect
+        ),
+    }
+}
+
+/// Converts a failed request to `url` into an error, explaining rate limits and certificate
+/// validation failures.
+fn request_error(url: &str, err: ureq::Error) -> anyhow::Error {
+    if let Some(limited) = github::RateLimited::from_error(&err) {
+        DownloadError::from(limited).into()
+    } else if is_certificate_error(&err) {
+        anyhow!(
+            "{err}\n\nThe TLS certificate presented for {url} could not be verified. If you are \
+             behind a proxy that inspects HTTPS traffic, set {FUELUP_CA_BUNDLE} or {SSL_CERT_FILE} \
+             to a PEM file containing the proxy's root certificate."
+        )
+    } else {
+        err.into()
+    }
 }
 
-pub fn build_agent() -> Result<ureq::Agent> {
-    let agent_builder = ureq::builder().user_agent("fuelup");
-    let proxy_result = env::var("http_proxy")
-        .or(env::var("HTTP_PROXY"))
-        .or(env::var("https_proxy"))
-        .or(env::var("HTTPS_PROXY"))
-        .or(env::var("all_proxy"))
-        .or(env::var("ALL_PROXY"));
+/// Whether a request failed because the server's certificate was rejected.
+fn is_certificate_error(err: &ureq::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+    while let Some(e) = source {
+        // rustls errors reach us wrapped in an `io::Error`, whose `source` skips the wrapped error.
+        let tls_error = e.downcast_ref::<rustls::Error>().or_else(|| {
+            e.downcast_ref::<std::io::Error>()
+                .and_then(|io| io.get_ref())
+                .and_then(|inner| inner.downcast_ref::<rustls::Error>())
+        });
+        if matches!(tls_error, Some(rustls::Error::InvalidCertificate(_))) {
+            return true;
+        }
+        source = e.source();
+    }
+    false
+}
 
-    if let Ok(proxy) = proxy_result {
-        return match ureq::Proxy::new(&proxy) {
-            Ok(proxy) => Ok(agent_builder.proxy(proxy).build()),
+/// Returns the delay requested by the `Retry-After` header of a failed response, if any.
+fn retry_after(err: &ureq::Error) -> Option<Duration> {
+    match err {
+        ureq::Error::Status(_, response) => response
+            .header("Retry-After")
+            .and_then(|h| h.trim().parse().ok())
+            .map(Duration::from_secs),
+        ureq::Error::Transport(_) => None,
+    }
+}
+
+/// Returns a pseudo-random duration of at most `max`. This only needs to spread retries apart, so
+/// the clock is a good enough source of randomness.
+fn jitter(max: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    max.mul_f64(f64::from(nanos) / 1e9)
+}
+
+/// Builds the HTTP client used for requests to `url`. Requests go through the proxy configured for
+/// the scheme of `url`, unless its host is excluded by `NO_PROXY`, and time out according to
+/// `FUELUP_CONNECT_TIMEOUT` and `FUELUP_READ_TIMEOUT`.
+pub fn build_agent(url: &str) -> Result<ureq::Agent> {
+    let timeout = |key, f: fn(&Settings) -> Option<u64>, default| {
+        Duration::from_secs(setting(key, f).unwrap_or(default))
+    };
+    let mut agent_builder = ureq::builder()
+        .user_agent("fuelup")
+        .timeout_connect(timeout(
+            FUELUP_CONNECT_TIMEOUT,
+            |s| s.connect_timeout_secs,
+            DEFAULT_CONNECT_TIMEOUT_SECS,
+        ))
+        .timeout_read(timeout(
+            FUELUP_READ_TIMEOUT,
+            |s| s.read_timeout_secs,
+            DEFAULT_READ_TIMEOUT_SECS,
+        ));
+
+    if let Some(ca_bundle) = ca_bundle() {
+        agent_builder = agent_builder.tls_config(tls_config(&ca_bundle)?);
+    }
+
+    if let Some(proxy) = select_proxy(url, |key| env::var(key).ok()) {
+        agent_builder = match ureq::Proxy::new(&proxy) {
+            Ok(proxy) => agent_builder.proxy(proxy),
             Err(err) => {
-                error!("Failed to build proxy with http_proxy={}, {}", proxy, err);
-                Err(err.into())
+                error!("Failed to build proxy with {}, {}", proxy, err);
+                return Err(err.into());
             }
         };
     }
@@ -121,144 +462,704 @@ pub fn build_agent() -> Result<ureq::Agent> {
     Ok(agent_builder.build())
 }
 
-pub fn tarball_name(tarball_prefix: &str, version: &Version, target: &TargetTriple) -> String {
+/// Returns the PEM bundle of extra root certificates to trust, if any. `FUELUP_CA_BUNDLE` takes
+/// precedence over the `ca_bundle` key in settings.toml, which takes precedence over
+/// `SSL_CERT_FILE`.
+fn ca_bundle() -> Option<PathBuf> {
+    setting(FUELUP_CA_BUNDLE, |s| s.ca_bundle.clone()).or_else(|| {
+        env::var_os(SSL_CERT_FILE)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// Builds a TLS configuration trusting the certificates in the PEM file at `ca_bundle` in addition
+/// to the bundled web PKI roots.
+fn tls_config(ca_bundle: &Path) -> Result<Arc<rustls::ClientConfig>> {
+    let pem = File::open(ca_bundle)
+        .with_context(|| format!("Failed to read CA bundle {}", ca_bundle.display()))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(pem))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to parse CA bundle {}", ca_bundle.display()))?;
+    if certs.is_empty() {
+        bail!("No certificates found in CA bundle {}", ca_bundle.display());
+    }
+
+    let mut roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let (added, ignored) = roots.add_parsable_certificates(certs);
+    if ignored > 0 {
+        warn!(
+            "Ignored {} invalid certificates in CA bundle {}",
+            ignored,
+            ca_bundle.display()
+        );
+    }
+    debug!(
+        "Trusting {} certificates from CA bundle {}",
+        added,
+        ca_bundle.display()
+    );
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+/// Returns the proxy to use for `url` from the environment variables read by `var`, following the
+/// curl conventions: `http_proxy` or `https_proxy` depending on the scheme, then `all_proxy`, with
+/// hosts listed in `no_proxy` bypassing the proxy. Both lowercase and uppercase names are accepted.
+fn select_proxy(url: &str, var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let var = |name: &str| {
+        var(name)
+            .or_else(|| var(&name.to_uppercase()))
+            .filter(|v| !v.is_empty())
+    };
+    let (scheme, _) = url.split_once("://")?;
+    let host = url_host(url)?;
+
+    if var("no_proxy").is_some_and(|no_proxy| is_no_proxy(host, &no_proxy)) {
+        return None;
+    }
+
+    match scheme.to_ascii_lowercase().as_str() {
+        "http" => var("http_proxy"),
+        "https" => var("https_proxy"),
+        _ => return None,
+    }
+    .or_else(|| var("all_proxy"))
+}
+
+/// Returns the host of `url`, without its port or credentials.
+fn url_host(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    match host_port.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next(),
+        None => host_port.split(':').next(),
+    }
+}
+
+/// Whether `host` matches an entry of the comma-separated `no_proxy` list. An entry matches the
+/// host itself and all of its subdomains, and `*` matches every host.
+fn is_no_proxy(host: &str, no_proxy: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    no_proxy
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            if entry == "*" {
+                return true;
+            }
+            let entry = entry.trim_start_matches('*').trim_start_matches('.');
+            // Strip the port of `host:port` entries, leaving IPv6 addresses alone.
+            let entry = match entry.split_once(':') {
+                Some((name, port)) if !port.contains(':') => name,
+                _ => entry.trim_start_matches('[').trim_end_matches(']'),
+            }
+            .to_ascii_lowercase();
+            host == entry || host.ends_with(&format!(".{entry}"))
+        })
+}
+
+/// Archive formats release assets can be published in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    #[default]
+    TarGz,
+    TarXz,
+    TarZst,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// The file extension of archives in this format, without the leading dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::TarGz => "tar.gz",
+            Self::TarXz => "tar.xz",
+            Self::TarZst => "tar.zst",
+            Self::Zip => "zip",
+        }
+    }
+
+    /// Detects the format of an archive from the extension of its file name.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let file_name = file_name.to_ascii_lowercase();
+        [
+            (".tar.gz", Self::TarGz),
+            (".tgz", Self::TarGz),
+            (".tar.xz", Self::TarXz),
+            (".txz", Self::TarXz),
+            (".tar.zst", Self::TarZst),
+            (".tzst", Self::TarZst),
+            (".zip", Self::Zip),
+        ]
+        .into_iter()
+        .find(|(extension, _)| file_name.ends_with(extension))
+        .map(|(_, format)| format)
+    }
+
+    /// Detects the format of an archive from the magic bytes at the start of its content.
+    pub fn from_magic(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::TarGz)
+        } else if bytes.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Self::TarXz)
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::TarZst)
+        } else if bytes.starts_with(b"PK\x03\x04") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// Detects the format of the archive at `path`, preferring its magic bytes over its extension
+    /// since mirrors don't always preserve file names.
+    fn detect(path: &Path) -> io::Result<Self> {
+        let mut magic = Vec::with_capacity(6);
+        File::open(path)?.take(6).read_to_end(&mut magic)?;
+        Ok(Self::from_magic(&magic)
+            .or_else(|| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(Self::from_file_name)
+            })
+            .unwrap_or_default())
+    }
+}
+
+impl FromStr for ArchiveFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim_start_matches('.') {
+            "tar.gz" | "tgz" => Ok(Self::TarGz),
+            "tar.xz" | "txz" => Ok(Self::TarXz),
+            "tar.zst" | "tzst" => Ok(Self::TarZst),
+            "zip" => Ok(Self::Zip),
+            _ => bail!("Unsupported archive format: {s}"),
+        }
+    }
+}
+
+pub fn tarball_name(
+    tarball_prefix: &str,
+    version: &Version,
+    target: &TargetTriple,
+    format: ArchiveFormat,
+) -> String {
+    let extension = format.extension();
     if tarball_prefix == "forc-binaries" {
-        format!("{tarball_prefix}-{target}.tar.gz")
+        format!("{tarball_prefix}-{target}.{extension}")
     } else {
-        format!("{tarball_prefix}-{version}-{target}.tar.gz")
+        format!("{tarball_prefix}-{version}-{target}.{extension}")
     }
 }
 
+/// Returns the latest version of the component `name`. Use a [`LatestVersions`] instead when
+/// looking up several components, so that the channel is only fetched once.
 pub fn get_latest_version(name: &str) -> Result<Version> {
-    let handle = build_agent()?;
+    LatestVersions::new().latest_version(name)
+}
+
+/// Returns the version of the latest fuelup release on GitHub.
+pub fn latest_fuelup_version() -> Result<Version> {
+    const FUELUP_RELEASES_API_URL: &str =
+        "https://api.github.com/repos/FuelLabs/fuelup/releases/latest";
+    let handle = build_agent(FUELUP_RELEASES_API_URL)?;
     let mut data = Vec::new();
-    if name == FUELUP {
-        const FUELUP_RELEASES_API_URL: &str =
-            "https://api.github.com/repos/FuelLabs/fuelup/releases/latest";
-        let resp = handle.get(FUELUP_RELEASES_API_URL).call()?;
-        resp.into_reader().read_to_end(&mut data)?;
-        let response: LatestReleaseApiResponse =
-            serde_json::from_str(&String::from_utf8_lossy(&data))?;
-
-        let version_str = response.tag_name.trim_start_matches('v');
-        let version = Version::parse(version_str)?;
-        Ok(version)
-    } else {
-        let resp = handle.get(CHANNEL_LATEST_URL).call()?;
-
-        resp.into_reader().read_to_end(&mut data)?;
-
-        if let Ok(channel) =
-            Channel::from_dist_channel(&DistToolchainDescription::from_str("latest")?)
-        {
-            channel
-                .pkg
-                .get(name)
-                .ok_or_else(|| {
-                    anyhow!(
-                        "'{name}' is not a valid, downloadable package in the 'latest' channel."
-                    )
-                })
-                .map(|p| p.version.clone())
-        } else {
-            bail!("Failed to get 'latest' channel")
-        }
-    }
+    let resp = RetryPolicy::from_env().call(FUELUP_RELEASES_API_URL, || {
+        github::api_request(&handle, FUELUP_RELEASES_API_URL)
+    })?;
+    resp.into_reader().read_to_end(&mut data)?;
+    let response: LatestReleaseApiResponse = serde_json::from_str(&String::from_utf8_lossy(&data))?;
+
+    let version_str = response.tag_name.trim_start_matches('v');
+    let version = Version::parse(version_str)?;
+    Ok(version)
 }
 
-fn unpack(tar_path: &Path, dst: &Path) -> Result<()> {
-    let tar_gz = File::open(tar_path)?;
-    let decompressed = GzDecoder::new(tar_gz);
-    let mut archive = Archive::new(decompressed);
+/// Unpacks the archive at `archive_path` into `dst` and removes the archive.
+fn unpack(archive_path: &Path, dst: &Path) -> Result<(), ExtractError> {
+    let result = unpack_archive(archive_path, dst, MAX_UNPACKED_SIZE);
+    let removed = fs::remove_file(archive_path);
+    result?;
+    removed.map_err(|source| ExtractError::RemoveArchive {
+        path: archive_path.to_path_buf(),
+        source,
+    })
+}
 
-    if let Err(e) = archive.unpack(dst) {
-        error!(
-            "{}. The archive could be corrupted or the release may not be ready yet",
-            e
-        );
-    };
+fn unpack_archive(archive_path: &Path, dst: &Path, limit: u64) -> Result<(), ExtractError> {
+    let format = ArchiveFormat::detect(archive_path)?;
+    let file = File::open(archive_path)?;
+    match format {
+        ArchiveFormat::TarGz => unpack_tar(Archive::new(GzDecoder::new(file)), dst, limit),
+        ArchiveFormat::TarXz => unpack_tar(Archive::new(XzDecoder::new(file)), dst, limit),
+        ArchiveFormat::TarZst => unpack_tar(Archive::new(zstd::Decoder::new(file)?), dst, limit),
+        ArchiveFormat::Zip => unpack_zip(file, dst, limit),
+    }
+}
+
+fn unpack_tar<R: Read>(
+    mut archive: Archive<R>,
+    dst: &Path,
+    limit: u64,
+) -> Result<(), ExtractError> {
+    let mut remaining = limit;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        check_entry_path(&path)?;
+
+        remaining = remaining
+            .checked_sub(entry.size())
+            .ok_or(ExtractError::TooLarge { limit })?;
+
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            let target = entry
+                .link_name()?
+                .ok_or_else(|| ExtractError::UnsafePath { path: path.clone() })?
+                .into_owned();
+            // Hard links are relative to the root of the archive, symlinks to their parent.
+            let base = if entry_type.is_hard_link() {
+                Path::new("")
+            } else {
+                path.parent().unwrap_or(Path::new(""))
+            };
+            check_link_target(&path, base, &target)?;
+        }
 
-    fs::remove_file(tar_path)?;
+        if let Some(parent) = path.parent() {
+            create_dir_inside(dst, parent, &path)?;
+        }
+        if !entry.unpack_in(dst)? {
+            return Err(ExtractError::UnsafePath { path });
+        }
+        if entry_type.is_symlink() {
+            check_link_on_disk(dst, &path)?;
+        }
+    }
     Ok(())
 }
 
-pub fn download(url: &str) -> Result<Vec<u8>> {
-    const RETRY_ATTEMPTS: u8 = 4;
-    const RETRY_DELAY_SECS: u64 = 3;
+fn unpack_zip(file: File, dst: &Path, limit: u64) -> Result<(), ExtractError> {
+    let mut archive = ZipArchive::new(file).map_err(io::Error::other)?;
+    let mut remaining = limit;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(io::Error::other)?;
+        let path = entry
+            .enclosed_name()
+            .ok_or_else(|| ExtractError::UnsafePath {
+                path: PathBuf::from(entry.name()),
+            })?;
+        check_entry_path(&path)?;
+
+        if entry.is_dir() {
+            create_dir_inside(dst, &path, &path)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            create_dir_inside(dst, parent, &path)?;
+        }
+        let out_path = dst.join(&path);
+
+        if entry.is_symlink() {
+            let mut target = String::new();
+            entry.read_to_string(&mut target)?;
+            let target = PathBuf::from(target);
+            check_link_target(&path, path.parent().unwrap_or(Path::new("")), &target)?;
+            #[cfg(unix)]
+            {
+                std::os::unix::fs::symlink(&target, &out_path)?;
+                check_link_on_disk(dst, &path)?;
+            }
+            continue;
+        }
 
-    let handle = build_agent()?;
+        // The sizes recorded in zip archives can't be trusted, so count what is actually written.
+        // Never write through an existing file, which could be a symlink.
+        let mut out_file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&out_path)?;
+        let written = io::copy(&mut (&mut entry).take(remaining + 1), &mut out_file)?;
+        remaining = remaining
+            .checked_sub(written)
+            .ok_or(ExtractError::TooLarge { limit })?;
+
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&out_path, fs::Permissions::from_mode(mode & 0o777))?;
+        }
+    }
+    Ok(())
+}
 
-    for _ in 1..RETRY_ATTEMPTS {
-        match handle.get(url).call() {
-            Ok(response) => {
-                let mut data = Vec::new();
-                response.into_reader().read_to_end(&mut data)?;
-                return Ok(data);
-            }
-            Err(ureq::Error::Status(404, r)) => {
-                // We've reached the download_file stage, which means the tag must be correct.
-                error!("Failed to download from {}", &url);
-                let retry: Option<u64> = r.header("retry-after").and_then(|h| h.parse().ok());
-                let retry = retry.unwrap_or(RETRY_DELAY_SECS);
-                info!("Retrying..");
-                thread::sleep(Duration::from_secs(retry));
-            }
-            Err(e) => {
-                // handle other status code and non-status code errors
-                bail!("Unexpected error: {}", e.to_string());
+/// Rejects archive entries with absolute paths or `..` components.
+fn check_entry_path(path: &Path) -> Result<(), ExtractError> {
+    if path
+        .components()
+        .all(|c| matches!(c, PathComponent::Normal(_) | PathComponent::CurDir))
+    {
+        Ok(())
+    } else {
+        Err(ExtractError::UnsafePath {
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+/// Rejects links whose target, resolved from `base` within the archive, leaves the archive.
+fn check_link_target(path: &Path, base: &Path, target: &Path) -> Result<(), ExtractError> {
+    let mut resolved = base.to_path_buf();
+    for component in target.components() {
+        let inside = match component {
+            PathComponent::Normal(name) => {
+                resolved.push(name);
+                true
             }
+            PathComponent::CurDir => true,
+            PathComponent::ParentDir => resolved.pop(),
+            PathComponent::RootDir | PathComponent::Prefix(_) => false,
+        };
+        if !inside {
+            return Err(ExtractError::UnsafeLink {
+                path: path.to_path_buf(),
+                target: target.to_path_buf(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks where an unpacked symlink actually points, as chains of links within the archive can
+/// lead out of it even though each link looks harmless on its own.
+fn check_link_on_disk(dst: &Path, path: &Path) -> Result<(), ExtractError> {
+    match fs::canonicalize(dst.join(path)) {
+        Ok(resolved) if !resolved.starts_with(fs::canonicalize(dst)?) => {
+            Err(ExtractError::UnsafeLink {
+                path: path.to_path_buf(),
+                target: resolved,
+            })
         }
+        // Dangling links can't be followed, and whatever they point to doesn't exist anyway.
+        _ => Ok(()),
     }
+}
 
-    bail!("Could not read file");
+/// Creates `dir` and its missing ancestors within `dst`, refusing to follow symlinks that lead
+/// out of `dst` on the way.
+fn create_dir_inside(dst: &Path, dir: &Path, path: &Path) -> Result<(), ExtractError> {
+    let root = fs::canonicalize(dst)?;
+    let mut current = dst.to_path_buf();
+    for component in dir.components() {
+        current.push(component);
+        match fs::symlink_metadata(&current) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                if !fs::canonicalize(&current).is_ok_and(|resolved| resolved.starts_with(&root)) {
+                    return Err(ExtractError::UnsafePath {
+                        path: path.to_path_buf(),
+                    });
+                }
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => fs::create_dir(&current)?,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
 }
 
-pub fn download_file(url: &str, path: &PathBuf) -> Result<()> {
-    const RETRY_ATTEMPTS: u8 = 4;
-    const RETRY_DELAY_SECS: u64 = 3;
+pub fn download(url: &str) -> Result<Vec<u8>> {
+    if let Some(path) = local_path(url) {
+        return fs::read(&path).with_context(|| format!("Failed to read {}", path.display()));
+    }
 
-    let handle = build_agent()?;
+    let handle = build_agent(url)?;
+    let response = RetryPolicy::from_env().call(url, || handle.get(url))?;
 
-    let mut file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)?;
+    let mut data = Vec::new();
+    response.into_reader().read_to_end(&mut data)?;
+    Ok(data)
+}
 
-    for _ in 1..RETRY_ATTEMPTS {
-        match handle.get(url).call() {
-            Ok(response) => {
-                if let Err(e) = write_response_with_progress_bar(
-                    response,
+/// Downloads the file at `url` to `path`, returning the hex-encoded SHA-256 digest of its contents.
+pub fn download_file(url: &str, path: &PathBuf) -> Result<String> {
+    if let Some(src) = local_path(url) {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let result = File::open(&src)
+            .with_context(|| format!("Failed to read {}", src.display()))
+            .and_then(|src_file| {
+                let total_size = src_file.metadata()?.len();
+                write_with_progress_bar(
+                    src_file,
+                    total_size,
                     &mut file,
                     &path.display().to_string(),
-                ) {
-                    fs::remove_file(path)?;
-                    return Err(e);
+                )
+            });
+        if result.is_err() {
+            fs::remove_file(path)?;
+        }
+        return result;
+    }
+
+    let tmp_dir = fuelup_tmp_dir();
+    ensure_dir_exists(&tmp_dir)?;
+    download_file_resumable(url, path, &tmp_dir)
+}
+
+/// Records where a partially downloaded file in `fuelup_tmp_dir()` came from, so that an
+/// interrupted download can be resumed instead of restarted.
+#[derive(Debug, Serialize, Deserialize)]
+struct PartialDownload {
+    url: String,
+    length: Option<u64>,
+}
+
+/// Returns the paths of the partial download of `url` and its sidecar, named by the hash of `url`
+/// so that downloads of different URLs with the same file name don't share them.
+fn partial_download_paths(tmp_dir: &Path, url: &str) -> (PathBuf, PathBuf) {
+    let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+    (
+        tmp_dir.join(format!("{key}.partial")),
+        tmp_dir.join(format!("{key}.partial.json")),
+    )
+}
+
+/// Marks a partial download as being written by this process, so that fuelup processes sharing a
+/// home never write to the same partial file. The lock is removed when dropped.
+struct PartialDownloadLock(PathBuf);
+
+impl PartialDownloadLock {
+    /// How long a partial download can go without being written to before its lock is assumed to
+    /// have been left behind by a process that was killed.
+    const STALE_AFTER: Duration = Duration::from_secs(10 * 60);
+
+    /// Locks the partial download at `partial`, returning `None` if another process holds the lock.
+    fn acquire(partial: &Path) -> Result<Option<Self>> {
+        let lock = partial.with_extension("lock");
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&lock) {
+                Ok(_) => return Ok(Some(Self(lock))),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    let last_written = [lock.as_path(), partial]
+                        .into_iter()
+                        .filter_map(|p| fs::metadata(p).and_then(|m| m.modified()).ok())
+                        .max();
+                    let is_stale = last_written
+                        .and_then(|t| t.elapsed().ok())
+                        .is_none_or(|age| age > Self::STALE_AFTER);
+                    if !is_stale {
+                        return Ok(None);
+                    }
+                    let _ = fs::remove_file(&lock);
                 }
-                return Ok(());
+                Err(e) => return Err(e.into()),
             }
-            Err(ureq::Error::Status(404, r)) => {
-                // We've reached download_file stage, which means the tag must be correct.
-                error!("Failed to download from {}", &url);
-                let retry: Option<u64> = r.header("retry-after").and_then(|h| h.parse().ok());
-                let retry = retry.unwrap_or(RETRY_DELAY_SECS);
-                info!("Retrying..");
-                thread::sleep(Duration::from_secs(retry));
+        }
+        Ok(None)
+    }
+}
+
+impl Drop for PartialDownloadLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Returns the number of bytes of `url` that were already downloaded to `partial`. A partial
+/// download left behind for a different URL, or one without a sidecar, is discarded.
+fn resume_offset(url: &str, partial: &Path, sidecar: &Path) -> u64 {
+    let recorded = fs::read(sidecar)
+        .ok()
+        .and_then(|s| serde_json::from_slice::<PartialDownload>(&s).ok());
+    let downloaded = fs::metadata(partial).map_or(0, |m| m.len());
+
+    match recorded {
+        Some(recorded) if recorded.url == url && recorded.length.is_none_or(|l| downloaded < l) => {
+            downloaded
+        }
+        _ => {
+            let _ = fs::remove_file(partial);
+            let _ = fs::remove_file(sidecar);
+            0
+        }
+    }
+}
+
+/// Moves `src` to `dst`, falling back to copying if they are on different filesystems.
+fn move_file(src: &Path, dst: &Path) -> Result<()> {
+    if fs::rename(src, dst).is_err() {
+        fs::copy(src, dst)?;
+        fs::remove_file(src)?;
+    }
+    Ok(())
+}
+
+/// Downloads `url` to `path` through a partial file in `tmp_dir`. If the transfer is interrupted,
+/// the partial file is kept and resumed with a `Range` request on the next attempt, or the next
+/// time the same URL is downloaded.
+fn download_file_resumable(url: &str, path: &Path, tmp_dir: &Path) -> Result<String> {
+    let (partial, sidecar) = partial_download_paths(tmp_dir, url);
+    if let Some(_lock) = PartialDownloadLock::acquire(&partial)? {
+        return download_through_partial(url, path, &partial, &sidecar);
+    }
+
+    // Another process is downloading the same URL, so this one downloads it separately.
+    debug!(
+        "{} is already being downloaded by another fuelup process",
+        url
+    );
+    let own_dir = tempfile::tempdir_in(tmp_dir)?;
+    let (partial, sidecar) = partial_download_paths(own_dir.path(), url);
+    download_through_partial(url, path, &partial, &sidecar)
+}
+
+fn download_through_partial(
+    url: &str,
+    path: &Path,
+    partial: &Path,
+    sidecar: &Path,
+) -> Result<String> {
+    let policy = RetryPolicy::from_env();
+    let handle = build_agent(url)?;
+    // Each request resumes from wherever the previous attempt got to.
+    let offset = Cell::new(0);
+    let request = || {
+        offset.set(resume_offset(url, partial, sidecar));
+        let request = handle.get(url);
+        if offset.get() == 0 {
+            return request;
+        }
+        info!(
+            "Resuming download of {} from {}",
+            url,
+            HumanBytes(offset.get())
+        );
+        request.set("Range", &format!("bytes={}-", offset.get()))
+    };
+    // We've reached the download_file stage, which means the tag must be correct and the release
+    // assets may just not be uploaded yet.
+    let retryable = |e: &ureq::Error| is_transient(e) || matches!(e, ureq::Error::Status(404, _));
+
+    // Requests that fail are retried by `policy`, transfers that are interrupted by this loop.
+    for attempt in 1..=policy.attempts {
+        let response = match policy.call_retrying(url, request, retryable) {
+            Ok(response) => response,
+            Err(e)
+                if offset.get() > 0
+                    && matches!(e.downcast_ref(), Some(ureq::Error::Status(416, _))) =>
+            {
+                // The partial download does not match the remote file anymore, so start over.
+                fs::remove_file(partial)?;
+                fs::remove_file(sidecar)?;
+                continue;
             }
+            Err(e) => return Err(e),
+        };
+        let offset = offset.get();
+
+        // Servers that do not support ranges respond with the whole file instead.
+        let resumed = offset > 0
+            && response.status() == 206
+            && response
+                .header("Content-Range")
+                .is_some_and(|r| r.starts_with(&format!("bytes {offset}-")));
+        let content_length = response
+            .header("Content-Length")
+            .and_then(|s| s.parse::<u64>().ok());
+        let record = PartialDownload {
+            url: url.to_string(),
+            length: content_length.map(|l| if resumed { offset + l } else { l }),
+        };
+        fs::write(sidecar, serde_json::to_vec(&record)?)?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(partial)?;
+        let hash = match write_response_with_progress_bar(
+            response,
+            &mut file,
+            &path.display().to_string(),
+        ) {
+            Ok(hash) => hash,
+            // Reading the response fails with an `io::Error`. Failing to write it, eg.
+            // because the disk is full, does not, and isn't retried.
+            Err(e) if e.downcast_ref::<io::Error>().is_none() => return Err(e),
             Err(e) => {
-                fs::remove_file(path)?;
-                // handle other status code and non-status code errors
-                bail!("Unexpected error: {}", e.to_string());
+                warn!("Download of {} was interrupted: {}", url, e);
+                policy.backoff(attempt, None);
+                continue;
             }
+        };
+
+        let downloaded = fs::metadata(partial)?.len();
+        if record.length.is_some_and(|l| l != downloaded) {
+            warn!("Download of {} ended early", url);
+            policy.backoff(attempt, None);
+            continue;
         }
+
+        // The digest of a resumed download must cover what was downloaded before, too.
+        let hash = if resumed { file_sha256(partial)? } else { hash };
+        move_file(partial, path)?;
+        fs::remove_file(sidecar)?;
+        return Ok(hash);
     }
 
-    fs::remove_file(path)?;
-    bail!("Could not download file");
+    bail!("Could not download file after {} attempts", policy.attempts);
 }
 
 pub fn download_file_and_unpack(download_cfg: &DownloadCfg, dst_dir_path: &Path) -> Result<()> {
+    let tarball_path = dst_dir_path.join(&download_cfg.tarball_name);
+    let cached = download_cfg
+        .hash
+        .as_deref()
+        .filter(|_| download_cache_enabled())
+        .and_then(cached_tarball_path);
+
+    match &cached {
+        Some(cached) if restore_cached_tarball(cached, &tarball_path) => {
+            info!("Using cached download of {}", &download_cfg.tarball_name);
+        }
+        _ => {
+            fetch_tarball(download_cfg, &tarball_path)?;
+            if let Some(cached) = &cached {
+                // Failing to cache the tarball shouldn't fail the install.
+                if let Err(e) = cache_tarball(&tarball_path, cached) {
+                    warn!("Failed to cache {}: {}", &download_cfg.tarball_name, e);
+                }
+            }
+        }
+    }
+
+    unpack(&tarball_path, dst_dir_path)?;
+
+    Ok(())
+}
+
+/// Downloads the tarball described by `download_cfg` to `tarball_path`, verifying its checksum
+/// when the hash is known.
+fn fetch_tarball(download_cfg: &DownloadCfg, tarball_path: &PathBuf) -> Result<()> {
     info!("Fetching binary from {}", &download_cfg.tarball_url);
     if download_cfg.hash.is_none() {
         warn!(
@@ -267,21 +1168,95 @@ pub fn download_file_and_unpack(download_cfg: &DownloadCfg, dst_dir_path: &Path)
         );
     }
 
-    let tarball_path = dst_dir_path.join(&download_cfg.tarball_name);
-
-    if let Err(e) = download_file(&download_cfg.tarball_url, &tarball_path) {
-        bail!(
+    let actual_hash = match download_file(&download_cfg.tarball_url, tarball_path) {
+        Ok(hash) => hash,
+        Err(e) => bail!(
             "Failed to download {} - {}. The release may not be ready yet.",
             &download_cfg.tarball_name,
             e
-        );
+        ),
     };
 
-    unpack(&tarball_path, dst_dir_path)?;
+    if let Some(expected_hash) = &download_cfg.hash {
+        if let Err(e) = verify_checksum(&download_cfg.tarball_name, expected_hash, &actual_hash) {
+            fs::remove_file(tarball_path)?;
+            return Err(e.into());
+        }
+        debug!("Verified checksum of {}", &download_cfg.tarball_name);
+    }
+
+    Ok(())
+}
+
+fn download_cache_enabled() -> bool {
+    setting(FUELUP_DOWNLOAD_CACHE, |s| s.download_cache).unwrap_or(false)
+}
+
+/// Returns where the tarball with the given SHA-256 hash is cached. Hashes come from channels,
+/// so anything but a hex digest is refused rather than used as a file name.
+fn cached_tarball_path(hash: &str) -> Option<PathBuf> {
+    (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| downloads_dir().join(hash.to_ascii_lowercase()))
+}
+
+/// Copies a cached tarball to `path`, returning whether it was found. The tarball is hashed again
+/// since the cache could have been tampered with or corrupted, and is discarded on a mismatch.
+fn restore_cached_tarball(cached: &Path, path: &Path) -> bool {
+    if !cached.is_file() {
+        return false;
+    }
+    let expected = cached.file_name().unwrap_or_default().to_string_lossy();
+    if !file_sha256(cached).is_ok_and(|actual| actual == expected) {
+        warn!("Discarding corrupted download {}", cached.display());
+        let _ = fs::remove_file(cached);
+        return false;
+    }
+    match fs::copy(cached, path) {
+        Ok(_) => true,
+        Err(e) => {
+            warn!("Failed to copy {}: {}", cached.display(), e);
+            false
+        }
+    }
+}
 
+/// Adds a verified tarball to the download cache.
+fn cache_tarball(tarball_path: &Path, cached: &Path) -> Result<()> {
+    let dir = downloads_dir();
+    ensure_dir_exists(&dir)?;
+    // Copy under a temporary name first so that concurrent installs never see a partial tarball.
+    let tmp = tempfile::NamedTempFile::new_in(&dir)?;
+    fs::copy(tarball_path, tmp.path())?;
+    tmp.persist(cached)?;
+    debug!("Cached {} at {}", tarball_path.display(), cached.display());
     Ok(())
 }
 
+/// Compares the SHA-256 digest of a downloaded tarball against the hash published in the channel.
+pub(crate) fn verify_checksum(
+    tarball_name: &str,
+    expected: &str,
+    actual: &str,
+) -> Result<(), DownloadError> {
+    if expected.eq_ignore_ascii_case(actual) {
+        Ok(())
+    } else {
+        Err(DownloadError::ChecksumMismatch {
+            tarball_name: tarball_name.to_string(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        })
+    }
+}
+
+/// Returns the hex-encoded SHA-256 digest of the file at `path`.
+pub(crate) fn file_sha256(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
 pub fn unpack_bins(dir: &Path, dst_dir: &Path) -> Result<Vec<PathBuf>> {
     let mut downloaded: Vec<PathBuf> = Vec::new();
     for entry in std::fs::read_dir(dir)? {
@@ -312,26 +1287,46 @@ pub fn unpack_bins(dir: &Path, dst_dir: &Path) -> Result<Vec<PathBuf>> {
     Ok(downloaded)
 }
 
-/// write Ok(Response) to provided writer with progress bar displaying writing status
+/// write Ok(Response) to provided writer with progress bar displaying writing status, returning the
+/// hex-encoded SHA-256 digest of the data written
 fn write_response_with_progress_bar<W: Write>(
     response: Response,
     writer: &mut W,
     target: &str,
-) -> Result<()> {
+) -> Result<String> {
     let total_size = response
         .header("Content-Length")
         .and_then(|s| s.parse::<u64>().ok())
         .unwrap_or(0);
+    write_with_progress_bar(response.into_reader(), total_size, writer, target)
+}
+
+/// Returns the progress bars shared by all downloads, so that components downloaded concurrently
+/// each get their own line instead of overwriting each other.
+fn progress_bars() -> &'static MultiProgress {
+    static PROGRESS_BARS: OnceLock<MultiProgress> = OnceLock::new();
+    PROGRESS_BARS.get_or_init(MultiProgress::new)
+}
+
+/// write the contents of `reader` to provided writer with progress bar displaying writing status,
+/// returning the hex-encoded SHA-256 digest of the data written
+fn write_with_progress_bar<R: Read, W: Write>(
+    reader: R,
+    total_size: u64,
+    writer: &mut W,
+    target: &str,
+) -> Result<String> {
     let mut downloaded_size = 0;
     let mut buffer = [0; 8192];
-    let progress_bar = ProgressBar::new(total_size);
+    let progress_bar = progress_bars().add(ProgressBar::new(total_size));
     progress_bar.set_style(
                 ProgressStyle::default_bar()
                     .template("[{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta}) - {msg:.green}")
                     .unwrap()
                     .progress_chars("##-"),
             );
-    let mut reader = progress_bar.wrap_read(response.into_reader());
+    let mut reader = progress_bar.wrap_read(reader);
+    let mut hasher = Sha256::new();
 
     loop {
         let bytes_read = reader.read(&mut buffer)?;
@@ -348,6 +1343,7 @@ fn write_response_with_progress_bar<W: Write>(
             }
             bail!("Something went wrong writing data to {}: {}", target, e)
         };
+        hasher.update(buf);
         downloaded_size += bytes_read as u64;
         progress_bar.set_position(downloaded_size);
     }
@@ -358,7 +1354,7 @@ fn write_response_with_progress_bar<W: Write>(
     }
     progress_bar.finish_with_message("Download complete");
     log_progress_bar(&progress_bar);
-    Ok(())
+    Ok(format!("{:x}", hasher.finalize()))
 }
 
 fn log_progress_bar(progress_bar: &ProgressBar) {
@@ -421,15 +1417,7 @@ pub fn fetch_fuels_version(cfg: &DownloadCfg) -> Result<String> {
         _ => bail!("invalid component to fetch fuels version for"),
     };
 
-    let handle = if let Ok(proxy) = env::var("http_proxy") {
-        ureq::builder()
-            .user_agent("fuelup")
-            .proxy(ureq::Proxy::new(proxy)?)
-            .build()
-    } else {
-        ureq::builder().user_agent("fuelup").build()
-    };
-
+    let handle = build_agent(&url)?;
     if let Ok(resp) = handle.get(&url).call() {
         let cargo_toml = toml_edit::DocumentMut::from_str(&resp.into_string()?)?;
         return fuels_version_from_toml(cargo_toml);
@@ -442,6 +1430,7 @@ pub fn fetch_fuels_version(cfg: &DownloadCfg) -> Result<String> {
 mod tests {
     use super::*;
     use dirs::home_dir;
+    use flate2::write::GzEncoder;
     use indoc::indoc;
     use std::io::{self, Result};
     use tempfile;
@@ -543,12 +1532,550 @@ mod tests {
             body,
         );
         let res = s.parse::<Response>().unwrap();
-        assert!(write_response_with_progress_bar(res, &mut data, "").is_ok());
+        let hash = write_response_with_progress_bar(res, &mut data, "")?;
+        assert_eq!(hash, format!("{:x}", Sha256::digest(&data)));
         let written_res = String::from_utf8(data)?;
         assert!(written_res.trim().eq(&body));
         Ok(())
     }
 
+    #[test]
+    fn test_rewrite_dist_url() {
+        let server = "https://mirror.example.com/fuel/";
+        assert_eq!(
+            rewrite_dist_url(
+                "https://raw.githubusercontent.com/FuelLabs/fuelup/gh-pages/channel-fuel-mainnet.toml",
+                server
+            ),
+            "https://mirror.example.com/fuel/channel-fuel-mainnet.toml"
+        );
+        assert_eq!(
+            rewrite_dist_url(
+                "https://github.com/FuelLabs/sway/releases/download/v0.17.0/forc-binaries-linux_amd64.tar.gz",
+                "file:///srv/fuel"
+            ),
+            "file:///srv/fuel/sway/releases/download/v0.17.0/forc-binaries-linux_amd64.tar.gz"
+        );
+        assert_eq!(
+            rewrite_dist_url("https://example.com/forc.tar.gz", server),
+            "https://example.com/forc.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_download_file_local() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let src = dir.path().join("src.tar.gz");
+        let dst = dir.path().join("dst.tar.gz");
+        fs::write(&src, b"fuel")?;
+
+        let hash = download_file(&format!("file://{}", src.display()), &dst)?;
+        assert_eq!(hash, format!("{:x}", Sha256::digest(b"fuel")));
+        assert_eq!(fs::read(&dst)?, b"fuel");
+
+        let missing = format!("file://{}", dir.path().join("missing").display());
+        assert!(download_file(&missing, &dst).is_err());
+        assert!(!dst.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_file_resumes_partial_download() -> anyhow::Result<()> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/forc.tar.gz", listener.local_addr()?);
+        let body = "fuel".repeat(64);
+
+        let server_body = body.clone();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let n = stream.read(&mut request).unwrap();
+            let request = String::from_utf8_lossy(&request[..n]).to_lowercase();
+            assert!(request.contains("range: bytes=100-"));
+            let rest = &server_body[100..];
+            write!(
+                stream,
+                "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes 100-{}/{}\r\n\r\n{}",
+                rest.len(),
+                server_body.len() - 1,
+                server_body.len(),
+                rest
+            )
+            .unwrap();
+        });
+
+        let dir = tempfile::tempdir()?;
+        let dst = dir.path().join("forc.tar.gz");
+        let (partial, sidecar) = partial_download_paths(dir.path(), &url);
+        fs::write(&partial, &body[..100])?;
+        let record = PartialDownload {
+            url: url.clone(),
+            length: Some(body.len() as u64),
+        };
+        fs::write(&sidecar, serde_json::to_vec(&record)?)?;
+
+        let hash = download_file_resumable(&url, &dst, dir.path())?;
+        server.join().unwrap();
+        assert_eq!(fs::read_to_string(&dst)?, body);
+        assert_eq!(hash, format!("{:x}", Sha256::digest(body.as_bytes())));
+        assert!(!partial.exists());
+        assert!(!sidecar.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_file_resumable_retries_failed_requests() -> anyhow::Result<()> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/forc.tar.gz", listener.local_addr()?);
+        let body = "fuel".repeat(64);
+
+        let server_body = body.clone();
+        let server = thread::spawn(move || {
+            let rest = &server_body[100..];
+            for response in [
+                "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                format!(
+                    "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes 100-{}/{}\r\nConnection: close\r\n\r\n{}",
+                    rest.len(),
+                    server_body.len() - 1,
+                    server_body.len(),
+                    rest
+                ),
+            ] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0; 1024];
+                let n = stream.read(&mut request).unwrap();
+                let request = String::from_utf8_lossy(&request[..n]).to_lowercase();
+                assert!(request.contains("range: bytes=100-"));
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let dir = tempfile::tempdir()?;
+        let dst = dir.path().join("forc.tar.gz");
+        let (partial, sidecar) = partial_download_paths(dir.path(), &url);
+        fs::write(&partial, &body[..100])?;
+        let record = PartialDownload {
+            url: url.clone(),
+            length: Some(body.len() as u64),
+        };
+        fs::write(&sidecar, serde_json::to_vec(&record)?)?;
+
+        download_file_resumable(&url, &dst, dir.path())?;
+        server.join().unwrap();
+        assert_eq!(fs::read_to_string(&dst)?, body);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resume_offset_discards_other_downloads() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let (partial, sidecar) =
+            partial_download_paths(dir.path(), "https://example.com/forc.tar.gz");
+        fs::write(&partial, b"fuel")?;
+        let record = PartialDownload {
+            url: "https://example.com/forc.tar.gz".to_string(),
+            length: Some(8),
+        };
+        fs::write(&sidecar, serde_json::to_vec(&record)?)?;
+
+        assert_eq!(
+            resume_offset("https://example.com/forc.tar.gz", &partial, &sidecar),
+            4
+        );
+        assert_eq!(
+            resume_offset("https://mirror.example.com/forc.tar.gz", &partial, &sidecar),
+            0
+        );
+        assert!(!partial.exists());
+        assert!(!sidecar.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_download_lock() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let (partial, _) = partial_download_paths(dir.path(), "https://example.com/a/forc.tar.gz");
+        let (other, _) = partial_download_paths(dir.path(), "https://example.com/b/forc.tar.gz");
+        assert_ne!(partial, other);
+
+        let lock = PartialDownloadLock::acquire(&partial)?;
+        assert!(lock.is_some());
+        assert!(PartialDownloadLock::acquire(&partial)?.is_none());
+        assert!(PartialDownloadLock::acquire(&other)?.is_some());
+        drop(lock);
+        let lock = PartialDownloadLock::acquire(&partial)?;
+        assert!(lock.is_some());
+
+        // A lock left behind by a process that was killed is taken over.
+        let stale = SystemTime::now() - PartialDownloadLock::STALE_AFTER * 2;
+        File::open(partial.with_extension("lock"))?.set_modified(stale)?;
+        std::mem::forget(lock);
+        assert!(PartialDownloadLock::acquire(&partial)?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_policy_delay() {
+        let policy = RetryPolicy {
+            attempts: 4,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+        let first = policy.delay(1, None);
+        assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+        let capped = policy.delay(10, None);
+        assert!(capped >= Duration::from_millis(500) && capped <= Duration::from_secs(1));
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(3600))),
+            RetryPolicy::MAX_RETRY_AFTER
+        );
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        let status = |code: u16| {
+            let response = format!("HTTP/1.1 {code} Status\r\n\r\n");
+            ureq::Error::Status(code, response.parse::<Response>().unwrap())
+        };
+
+        assert!(is_transient(&status(429)));
+        assert!(is_transient(&status(502)));
+        assert!(is_transient(&status(503)));
+        assert!(!is_transient(&status(404)));
+        assert!(!is_transient(&status(403)));
+
+        let response = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 7\r\n\r\n";
+        let throttled = ureq::Error::Status(429, response.parse::<Response>().unwrap());
+        assert_eq!(retry_after(&throttled), Some(Duration::from_secs(7)));
+        assert_eq!(retry_after(&status(503)), None);
+    }
+
+    #[test]
+    fn test_request_error_rate_limited() {
+        let status = |code: u16, headers: &str| {
+            let response = format!("HTTP/1.1 {code} Status\r\n{headers}\r\n");
+            ureq::Error::Status(code, response.parse::<Response>().unwrap())
+        };
+
+        let limited = status(
+            403,
+            "X-RateLimit-Remaining: 0\r\nX-RateLimit-Reset: 1700000000\r\n",
+        );
+        assert!(request_error("https://api.github.com", limited)
+            .downcast_ref::<DownloadError>()
+            .is_some_and(|e| matches!(e, DownloadError::RateLimited(_))));
+
+        let forbidden = status(
+            403,
+            "X-RateLimit-Remaining: 42\r\nX-RateLimit-Reset: 1700000000\r\n",
+        );
+        assert!(request_error("https://api.github.com", forbidden)
+            .downcast_ref::<DownloadError>()
+            .is_none());
+    }
+
+    #[test]
+    fn test_retry_policy_retries_transient_errors() -> anyhow::Result<()> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/channel.toml", listener.local_addr()?);
+
+        let server = thread::spawn(move || {
+            for response in [
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Length: 4\r\nConnection: close\r\n\r\nfuel",
+            ] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0; 1024];
+                let _ = stream.read(&mut request).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let policy = RetryPolicy {
+            attempts: 2,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        };
+        let handle = build_agent(&url)?;
+        let response = policy.call(&url, || handle.get(&url))?;
+        server.join().unwrap();
+        assert_eq!(response.into_string()?, "fuel");
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_proxy() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        let vars = env(&[
+            ("http_proxy", "http://http-proxy:3128"),
+            ("HTTPS_PROXY", "http://https-proxy:3128"),
+            ("NO_PROXY", "localhost, .internal.example.com,10.0.0.1:8080"),
+        ]);
+
+        assert_eq!(
+            select_proxy("http://github.com/FuelLabs", vars).as_deref(),
+            Some("http://http-proxy:3128")
+        );
+        assert_eq!(
+            select_proxy("https://github.com/FuelLabs", vars).as_deref(),
+            Some("http://https-proxy:3128")
+        );
+        assert_eq!(select_proxy("https://localhost:8000/", vars), None);
+        assert_eq!(
+            select_proxy("https://mirror.internal.example.com/fuel", vars),
+            None
+        );
+        assert_eq!(select_proxy("http://user@10.0.0.1/fuel", vars), None);
+        assert!(select_proxy("https://notinternal.example.com", vars).is_some());
+        assert_eq!(select_proxy("file:///srv/fuel", vars), None);
+
+        let all = env(&[("ALL_PROXY", "socks5://proxy:1080"), ("no_proxy", "*")]);
+        assert_eq!(select_proxy("https://github.com", all), None);
+        let all = env(&[("all_proxy", "socks5://proxy:1080")]);
+        assert_eq!(
+            select_proxy("https://github.com", all).as_deref(),
+            Some("socks5://proxy:1080")
+        );
+    }
+
+    #[test]
+    fn test_tls_config() -> anyhow::Result<()> {
+        let ca_bundle = env::current_dir()?.join("tests/ca-bundle-example.pem");
+        assert!(tls_config(&ca_bundle).is_ok());
+
+        let dir = tempfile::tempdir()?;
+        let empty = dir.path().join("empty.pem");
+        fs::write(&empty, "")?;
+        assert!(tls_config(&empty).is_err());
+        assert!(tls_config(&dir.path().join("missing.pem")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_archive_format_detection() {
+        assert_eq!(
+            ArchiveFormat::from_file_name("forc-0.1.0-x86_64-unknown-linux-gnu.tar.gz"),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_file_name("tool-aarch64-apple-darwin.TXZ"),
+            Some(ArchiveFormat::TarXz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_file_name("tool.tar.zst"),
+            Some(ArchiveFormat::TarZst)
+        );
+        assert_eq!(
+            ArchiveFormat::from_file_name("tool.zip"),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(ArchiveFormat::from_file_name("tool.tar"), None);
+
+        assert_eq!(
+            ArchiveFormat::from_magic(&[0x1f, 0x8b, 0x08]),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_magic(b"\xfd7zXZ\x00"),
+            Some(ArchiveFormat::TarXz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_magic(&[0x28, 0xb5, 0x2f, 0xfd]),
+            Some(ArchiveFormat::TarZst)
+        );
+        assert_eq!(
+            ArchiveFormat::from_magic(b"PK\x03\x04"),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(ArchiveFormat::from_magic(b"fuel"), None);
+
+        assert_eq!("zip".parse::<ArchiveFormat>().unwrap(), ArchiveFormat::Zip);
+        assert!("rar".parse::<ArchiveFormat>().is_err());
+    }
+
+    #[test]
+    fn test_tarball_name_extension() {
+        let target = TargetTriple::from_host().unwrap();
+        let version = Version::parse("0.1.0").unwrap();
+        assert_eq!(
+            tarball_name("forc-tool", &version, &target, ArchiveFormat::Zip),
+            format!("forc-tool-0.1.0-{target}.zip")
+        );
+        assert_eq!(
+            tarball_name("forc-binaries", &version, &target, ArchiveFormat::TarGz),
+            format!("forc-binaries-{target}.tar.gz")
+        );
+    }
+
+    #[test]
+    fn test_unpack_detects_format_from_magic() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        // Name the archive as gzip to check the content takes precedence over the extension.
+        let archive_path = dir.path().join("forc-tool.tar.gz");
+        let mut builder =
+            tar::Builder::new(xz2::write::XzEncoder::new(File::create(&archive_path)?, 6));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, "forc-tool", &b"fuel"[..])?;
+        builder.into_inner()?.finish()?;
+
+        let dst = dir.path().join("out");
+        fs::create_dir(&dst)?;
+        unpack(&archive_path, &dst)?;
+        assert_eq!(fs::read(dst.join("forc-tool"))?, b"fuel");
+        assert!(!archive_path.exists());
+        Ok(())
+    }
+
+    /// Writes a gzipped tarball with the entries added by `f` to `path`.
+    fn write_tarball(
+        path: &Path,
+        f: impl FnOnce(&mut tar::Builder<GzEncoder<File>>) -> Result<()>,
+    ) -> Result<()> {
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(path)?,
+            flate2::Compression::default(),
+        ));
+        f(&mut builder)?;
+        builder.into_inner()?.finish()?;
+        Ok(())
+    }
+
+    fn unpack_err(
+        f: impl FnOnce(&mut tar::Builder<GzEncoder<File>>) -> Result<()>,
+    ) -> ExtractError {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("forc-tool.tar.gz");
+        write_tarball(&archive_path, f).unwrap();
+        let dst = dir.path().join("out");
+        fs::create_dir(&dst).unwrap();
+        let error = unpack(&archive_path, &dst).unwrap_err();
+        assert!(!dir.path().join("outside").exists());
+        error
+    }
+
+    #[test]
+    fn test_unpack_rejects_path_traversal() {
+        let error = unpack_err(|builder| {
+            let mut header = tar::Header::new_gnu();
+            // `set_path` refuses `..`, so write the name like a malicious archive would.
+            header.as_old_mut().name[..10].copy_from_slice(b"../outside");
+            header.set_size(4);
+            header.set_cksum();
+            builder.append(&header, &b"fuel"[..])
+        });
+        assert!(
+            matches!(&error, ExtractError::UnsafePath { path } if path == Path::new("../outside")),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_unpack_rejects_escaping_symlink() {
+        let error = unpack_err(|builder| {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            builder.append_link(&mut header, "bin/forc", "../../outside")
+        });
+        assert!(matches!(error, ExtractError::UnsafeLink { .. }), "{error}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unpack_rejects_symlink_chain() {
+        // Each link stays within the archive on its own, but `e` resolves through `d/l` to the
+        // parent of the destination.
+        let error = unpack_err(|builder| {
+            for (path, target) in [("d/l", ".."), ("e", "d/l/..")] {
+                let mut header = tar::Header::new_gnu();
+                header.set_entry_type(tar::EntryType::Symlink);
+                header.set_size(0);
+                builder.append_link(&mut header, path, target)?;
+            }
+            Ok(())
+        });
+        assert!(
+            matches!(&error, ExtractError::UnsafeLink { path, .. } if path == Path::new("e")),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_unpack_size_limit() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let archive_path = dir.path().join("forc-tool.tar.gz");
+        write_tarball(&archive_path, |builder| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(4);
+            header.set_cksum();
+            builder.append_data(&mut header, "forc-tool", &b"fuel"[..])
+        })?;
+
+        let dst = dir.path().join("out");
+        fs::create_dir(&dst)?;
+        let error = unpack_archive(&archive_path, &dst, 3).unwrap_err();
+        assert!(matches!(error, ExtractError::TooLarge { limit: 3 }));
+        unpack_archive(&archive_path, &dst, 4)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_unpack_zip() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored)
+            .unix_permissions(0o755);
+
+        let archive_path = dir.path().join("forc-tool.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path)?);
+        writer.start_file("forc-tool/forc-tool", options)?;
+        writer.write_all(b"fuel")?;
+        writer.finish()?;
+
+        let dst = dir.path().join("out");
+        fs::create_dir(&dst)?;
+        unpack(&archive_path, &dst)?;
+        assert_eq!(fs::read(dst.join("forc-tool/forc-tool"))?, b"fuel");
+
+        let archive_path = dir.path().join("evil.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path)?);
+        writer.start_file("../outside", options)?;
+        writer.write_all(b"fuel")?;
+        writer.finish()?;
+
+        let error = unpack(&archive_path, &dst).unwrap_err();
+        assert!(matches!(error, ExtractError::UnsafePath { .. }), "{error}");
+        assert!(!dir.path().join("outside").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let actual = format!("{:x}", Sha256::digest(b"fuel"));
+        assert!(verify_checksum("forc.tar.gz", &actual, &actual).is_ok());
+        assert!(verify_checksum("forc.tar.gz", &actual.to_uppercase(), &actual).is_ok());
+
+        let error = verify_checksum("forc.tar.gz", "deadbeef", &actual).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("Checksum mismatch for forc.tar.gz: expected deadbeef, got {actual}")
+        );
+    }
+
     #[test]
     fn test_write_response_with_progress_bar_fail() {
         let mut mock_writer = MockWriter;
@@ -573,8 +2100,10 @@ mod tests {
     #[test]
     fn test_agent() -> anyhow::Result<()> {
         // this test case is used to illustrate the bug of ureq that sometimes doesn't return "Content-Length" header
-        let handle = build_agent()?;
-        let response = handle.get("https://raw.githubusercontent.com/FuelLabs/fuelup/gh-pages/channel-fuel-testnet.toml").call()?;
+        let url =
+            "https://raw.githubusercontent.com/FuelLabs/fuelup/gh-pages/channel-fuel-testnet.toml";
+        let handle = build_agent(url)?;
+        let response = handle.get(url).call()?;
         assert!(response.header("Content-Length").is_none());
         Ok(())
     }
@@ -591,11 +2120,14 @@ mod tests {
                         url: "https://example.com/forc-0.1.0-x86_64-unknown-linux-gnu.tar.gz"
                             .to_string(),
                         hash: "hash".to_string(),
+                        size: None,
                     },
                 );
                 map
             },
             fuels_version: None,
+            release_notes: None,
+            min_fuelup_version: None,
         };
         let error = DownloadCfg::from_package("forc", &package)
             .expect_err("Expected error due to missing target");
@@ -607,6 +2139,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_download_cfg_with_artifacts_dir() {
+        let cfg = DownloadCfg {
+            name: "fuel-core".to_string(),
+            target: TargetTriple::from_host().unwrap(),
+            version: Version::parse("0.9.4").unwrap(),
+            tarball_name: "fuel-core-0.9.4-x86_64-unknown-linux-gnu.tar.gz".to_string(),
+            tarball_url: "https://github.com/FuelLabs/fuel-core/releases/download/v0.9.4/fuel-core-0.9.4-x86_64-unknown-linux-gnu.tar.gz".to_string(),
+            hash: Some("hash".to_string()),
+            fuels_version: None,
+        };
+        assert!(!cfg.is_local());
+
+        let cfg = cfg.with_artifacts_dir(Path::new("/srv/artifacts"));
+        assert!(cfg.is_local());
+        assert_eq!(
+            cfg.tarball_url,
+            "file:///srv/artifacts/fuel-core-0.9.4-x86_64-unknown-linux-gnu.tar.gz"
+        );
+        assert_eq!(cfg.hash, Some("hash".to_string()));
+    }
+
     #[test]
     fn test_fetch_fuels_version_forc_wallet_migration() {
         // Test legacy forc-wallet version (should fetch from forc-wallet repo)
@@ -617,6 +2171,7 @@ mod tests {
             tarball_name: "dummy".to_string(),
             tarball_url: "dummy".to_string(),
             hash: None,
+            fuels_version: None,
         };
 
         let legacy_result = fetch_fuels_version(&legacy_cfg);
@@ -633,6 +2188,7 @@ mod tests {
             tarball_name: "dummy".to_string(),
             tarball_url: "dummy".to_string(),
             hash: None,
+            fuels_version: None,
         };
 
         let new_result = fetch_fuels_version(&new_cfg);
@@ -1,9 +1,13 @@
 use crate::commands::{
+    bisect::{self, BisectCommand},
+    cache::{self, CacheCommand},
+    channel::{self, ChannelCommand},
     check::{self, CheckCommand},
     completions::{self, CompletionsCommand},
     component::{self, ComponentCommand},
     default::{self, DefaultCommand},
     fuelup::{self, FuelupCommand},
+    mirror::{self, MirrorCommand},
     toolchain::{self, ToolchainCommand},
     upgrade::{self, UpgradeCommand},
 };
@@ -20,6 +24,14 @@ pub struct Cli {
 
 #[derive(Debug, Parser)]
 enum Commands {
+    /// Find the first dated toolchain a command fails with, by binary search between two of them
+    Bisect(BisectCommand),
+    /// Manage the cache of downloaded tarballs and channels
+    #[clap(subcommand)]
+    Cache(CacheCommand),
+    /// Register custom channels to install toolchains from
+    #[clap(subcommand)]
+    Channel(ChannelCommand),
     /// Check for updates to Fuel toolchains and fuelup
     Check(CheckCommand),
     /// Generate shell completions
@@ -32,6 +44,8 @@ enum Commands {
     /// Manage your fuelup installation.
     #[clap(name = "self", subcommand)]
     Fuelup(FuelupCommand),
+    /// Download a channel and all of its artifacts into a directory that can be used as a mirror
+    Mirror(MirrorCommand),
     /// Install new toolchains or modify/query installed toolchains
     #[clap(subcommand)]
     Toolchain(ToolchainCommand),
@@ -47,6 +61,9 @@ pub fn fuelup_cli() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
+        Commands::Bisect(command) => bisect::exec(command),
+        Commands::Cache(command) => cache::exec(command),
+        Commands::Channel(command) => channel::exec(command),
         Commands::Check(command) => check::exec(command),
         Commands::Completions(command) => completions::exec(command),
         Commands::Component(command) => component::exec(command),
@@ -55,6 +72,7 @@ pub fn fuelup_cli() -> Result<()> {
             FuelupCommand::Update(update) => fuelup::update_exec(update.force),
             FuelupCommand::Uninstall(remove) => fuelup::remove_exec(remove.force),
         },
+        Commands::Mirror(command) => mirror::exec(command),
         Commands::Show => fuelup_show::show(),
         Commands::Toolchain(command) => toolchain::exec(command),
         Commands::Update => fuelup_update::update(),
@@ -1,6 +1,7 @@
 #![cfg_attr(test, allow(clippy::indexing_slicing))]
 
 pub mod channel;
+pub mod channel_cache;
 pub mod commands;
 pub mod config;
 pub mod constants;
@@ -14,7 +15,9 @@ pub mod path;
 pub mod proxy_cli;
 pub mod settings;
 pub mod shell;
+pub mod signature;
 pub mod store;
 pub mod target_triple;
 pub mod toolchain;
 pub mod toolchain_override;
+pub mod versions;
@@ -1,4 +1,4 @@
-use crate::path::{fuelup_log_dir, FUELUP_DIR, FUELUP_HOME};
+use crate::path::{fuelup_bin_dir, fuelup_log_dir, FUELUP_HOME};
 use std::env;
 use tracing::{debug, level_filters::LevelFilter};
 use tracing_appender::non_blocking::WorkerGuard;
@@ -13,12 +13,11 @@ pub fn log_command() {
 
 pub fn log_environment() {
     if let Some(val) = env::var_os("PATH") {
-        if let Some(fuelup_path) =
-            env::split_paths(&val).find(|p| p.to_string_lossy().contains(FUELUP_DIR))
-        {
+        let fuelup_bin_dir = fuelup_bin_dir();
+        if let Some(fuelup_path) = env::split_paths(&val).find(|p| p == &fuelup_bin_dir) {
             debug!("PATH includes {}", fuelup_path.to_string_lossy());
         } else {
-            debug!("PATH does not include {}", FUELUP_DIR);
+            debug!("PATH does not include {}", fuelup_bin_dir.display());
         }
     }
     if let Some(val) = env::var_os(FUELUP_HOME) {
@@ -0,0 +1,166 @@
+use crate::{
+    channel::{Channel, ChannelIndex},
+    commands::bisect::BisectCommand,
+    ops::fuelup_channel::{diff_channels, format_package_diff},
+    toolchain::{DistToolchainDescription, DistToolchainName, Toolchain},
+};
+use anyhow::{bail, Result};
+use std::{env, process::Command, str::FromStr};
+use time::Date;
+use tracing::info;
+
+/// Parses a dated toolchain description, returning its channel and date.
+fn parse_dated(toolchain: &str) -> Result<(DistToolchainName, Date)> {
+    let description = DistToolchainDescription::from_str(toolchain)?;
+    match description.date {
+        Some(date) => Ok((description.name, date)),
+        None => bail!(
+            "'{}' is not a dated toolchain, eg. nightly-2024-04-01",
+            toolchain
+        ),
+    }
+}
+
+/// Binary-searches the `dates` between `good` and `bad` for the first date that `is_good` rejects,
+/// passing it the number of dates left to test. Returns the last good date and the first bad date.
+fn search<F>(good: Date, bad: Date, dates: &[Date], mut is_good: F) -> Result<(Date, Date)>
+where
+    F: FnMut(Date, usize) -> Result<bool>,
+{
+    let mut candidates = vec![good];
+    candidates.extend(dates.iter().filter(|d| **d > good && **d < bad));
+    candidates.push(bad);
+
+    // Invariant: candidates[lo] is good and candidates[hi] is bad.
+    let (mut lo, mut hi) = (0, candidates.len() - 1);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        let Some(date) = candidates.get(mid) else {
+            break;
+        };
+        if is_good(*date, hi - lo - 1)? {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok((
+        candidates.get(lo).copied().unwrap_or(good),
+        candidates.get(hi).copied().unwrap_or(bad),
+    ))
+}
+
+/// Installs the toolchain described by `name` and `date` if needed, and runs `command` with its
+/// executables first in `PATH`. Returns whether the command succeeded.
+fn run_with_toolchain(name: &DistToolchainName, date: Date, command: &[String]) -> Result<bool> {
+    let description = DistToolchainDescription {
+        name: name.clone(),
+        date: Some(date),
+        target: None,
+    };
+    let toolchain = Toolchain::from_path(&description.to_string());
+    toolchain.install_if_nonexistent(&description)?;
+    if !toolchain.exists() {
+        bail!("Could not install toolchain '{}'", description);
+    }
+
+    let mut paths = vec![toolchain.bin_path.clone()];
+    if let Some(path) = env::var_os("PATH") {
+        paths.extend(env::split_paths(&path));
+    }
+    let Some((program, args)) = command.split_first() else {
+        bail!("No command was given to run");
+    };
+    let status = match Command::new(program)
+        .args(args)
+        .env("PATH", env::join_paths(paths)?)
+        .status()
+    {
+        Ok(status) => status,
+        Err(e) => bail!("Failed to run '{}': {}", program, e),
+    };
+    Ok(status.success())
+}
+
+pub fn bisect(command: BisectCommand) -> Result<()> {
+    let BisectCommand { good, bad, command } = command;
+
+    let (name, good_date) = parse_dated(&good)?;
+    let (bad_name, bad_date) = parse_dated(&bad)?;
+    if name != bad_name {
+        bail!("'{}' and '{}' are from different channels", good, bad);
+    }
+    if good_date >= bad_date {
+        bail!("The good toolchain must be older than the bad one");
+    }
+
+    let index = ChannelIndex::from_dist(&name)?;
+    let (last_good, first_bad) = search(good_date, bad_date, &index.dates, |date, remaining| {
+        info!("Testing {name}-{date} ({remaining} toolchains left to test)");
+        let is_good = run_with_toolchain(&name, date, &command)?;
+        info!("{name}-{date} is {}", if is_good { "good" } else { "bad" });
+        Ok(is_good)
+    })?;
+
+    info!("\n{name}-{first_bad} is the first bad toolchain, after {name}-{last_good}");
+
+    let channel = |date| {
+        Channel::from_dist_channel(&DistToolchainDescription {
+            name: name.clone(),
+            date: Some(date),
+            target: None,
+        })
+    };
+    let packages = diff_channels(&channel(last_good)?, &channel(first_bad)?);
+    if packages.is_empty() {
+        info!("The channels of both toolchains have the same packages");
+    } else {
+        let lines = packages.iter().map(format_package_diff).collect::<Vec<_>>();
+        info!("Changes:\n{}", lines.join("\n"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DATE_FORMAT;
+
+    fn date(d: &str) -> Date {
+        Date::parse(d, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_search() {
+        let dates = [
+            "2024-05-01",
+            "2024-05-02",
+            "2024-05-04",
+            "2024-05-05",
+            "2024-05-08",
+        ]
+        .map(date);
+        let first_bad = date("2024-05-05");
+        let mut tested = Vec::new();
+
+        let result = search(date("2024-05-01"), date("2024-05-08"), &dates, |d, _| {
+            tested.push(d);
+            Ok(d < first_bad)
+        })
+        .unwrap();
+
+        assert_eq!(result, (date("2024-05-04"), first_bad));
+        assert!(tested.len() <= 2);
+        assert!(!tested.contains(&date("2024-05-01")));
+        assert!(!tested.contains(&date("2024-05-08")));
+    }
+
+    #[test]
+    fn test_search_adjacent() {
+        let result = search(date("2024-05-01"), date("2024-05-02"), &[], |_, _| {
+            panic!("nothing to test")
+        })
+        .unwrap();
+        assert_eq!(result, (date("2024-05-01"), date("2024-05-02")));
+    }
+}
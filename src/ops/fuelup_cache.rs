@@ -0,0 +1,54 @@
+use crate::path::{channel_cache_dir, downloads_dir};
+use anyhow::Result;
+use indicatif::HumanBytes;
+use std::{fs, path::Path};
+
+/// Removes a cache directory, returning the number and total size of the entries it held, or
+/// `None` if there was nothing to remove. Metadata kept next to entries isn't counted.
+fn remove_cache_dir(dir: &Path) -> Result<Option<(u64, u64)>> {
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+
+    let (mut count, mut size) = (0, 0);
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() && entry.path().extension().is_none_or(|ext| ext != "json") {
+            count += 1;
+            size += metadata.len();
+        }
+    }
+    fs::remove_dir_all(dir)?;
+    Ok(Some((count, size)))
+}
+
+fn plural(count: u64) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+pub fn clean() -> Result<()> {
+    let downloads = remove_cache_dir(&downloads_dir())?;
+    let channels = remove_cache_dir(&channel_cache_dir())?;
+    if downloads.is_none() && channels.is_none() {
+        println!("The download cache is already empty");
+        return Ok(());
+    }
+
+    if let Some((count, size)) = downloads {
+        println!(
+            "Removed {} cached download{} ({})",
+            count,
+            plural(count),
+            HumanBytes(size)
+        );
+    }
+    if let Some((count, _)) = channels {
+        println!("Removed {} cached channel file{}", count, plural(count));
+    }
+    Ok(())
+}
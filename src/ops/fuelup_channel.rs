@@ -0,0 +1,376 @@
+use crate::{
+    channel::{custom_channels, Channel},
+    commands::channel::{AddCommand, DiffCommand, RemoveCommand},
+    download::download,
+    fmt::{format_toolchain_with_target, println_warn},
+    path::settings_file,
+    settings::SettingsFile,
+    toolchain::{is_valid_channel_name, DistToolchainDescription, Toolchain},
+    versions::compare_versions,
+};
+use anyhow::{bail, Context, Result};
+use semver::Version;
+use serde::Serialize;
+use std::{cmp::Ordering, collections::BTreeSet, path::Path, str::FromStr};
+use tracing::info;
+
+/// How a package differs between two channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    Added,
+    Removed,
+    Upgraded,
+    Downgraded,
+    /// The version is the same, but the `fuels_version` it is compatible with changed.
+    FuelsVersionChanged,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct PackageDiff {
+    pub name: String,
+    pub change: Change,
+    pub from_version: Option<Version>,
+    pub to_version: Option<Version>,
+    pub from_fuels_version: Option<String>,
+    pub to_fuels_version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChannelDiff<'a> {
+    from: &'a str,
+    to: &'a str,
+    packages: Vec<PackageDiff>,
+}
+
+/// Returns the URL of a channel given either as a URL or as a path to a local file.
+fn channel_url(url: &str) -> Result<String> {
+    if url.contains("://") {
+        return Ok(url.to_string());
+    }
+    let path = Path::new(url)
+        .canonicalize()
+        .with_context(|| format!("Failed to read channel {url}"))?;
+    Ok(format!("file://{}", path.display()))
+}
+
+pub fn add(command: AddCommand) -> Result<()> {
+    let AddCommand { name, url } = command;
+    if !is_valid_channel_name(&name) {
+        bail!(
+            "Invalid channel name '{}'. Names may contain letters, digits, '-' and '_', must not \
+             start with the name of an official channel and must not end with a date or target.",
+            name
+        );
+    }
+
+    let channels = custom_channels();
+    let toolchain = Toolchain::from_path(&format_toolchain_with_target(&name));
+    if toolchain.exists() && !channels.contains_key(&name) {
+        bail!(
+            "Cannot add channel '{}' as toolchain '{}' already exists",
+            name,
+            toolchain.name
+        );
+    }
+
+    // Make sure the channel can be read before registering it.
+    let url = channel_url(&url)?;
+    let toml = download(&url).with_context(|| format!("Could not read {url}"))?;
+    Channel::from_toml(&String::from_utf8(toml)?)
+        .with_context(|| format!("{url} is not a valid channel"))?;
+
+    SettingsFile::new(settings_file()).with_mut(|s| {
+        s.channels
+            .get_or_insert_with(Default::default)
+            .insert(name.clone(), url.clone());
+        Ok(())
+    })?;
+
+    let action = if channels.contains_key(&name) {
+        "Updated"
+    } else {
+        "Added"
+    };
+    info!(
+        "{action} channel '{name}' at {url}
+Install it with `fuelup toolchain install {name}`"
+    );
+    Ok(())
+}
+
+pub fn remove(command: RemoveCommand) -> Result<()> {
+    let RemoveCommand { name } = command;
+    if !custom_channels().contains_key(&name) {
+        bail!("Channel '{}' is not registered", name);
+    }
+
+    SettingsFile::new(settings_file()).with_mut(|s| {
+        if let Some(channels) = &mut s.channels {
+            channels.remove(&name);
+        }
+        Ok(())
+    })?;
+    info!("Removed channel '{}'", name);
+
+    let toolchain = Toolchain::from_path(&format_toolchain_with_target(&name));
+    if toolchain.exists() {
+        println_warn(format!(
+            "Toolchain '{0}' is still installed. Run `fuelup toolchain uninstall {0}` to remove it.",
+            toolchain.name
+        ));
+    }
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let channels = custom_channels();
+    if channels.is_empty() {
+        info!("No channels are registered. Add one with `fuelup channel add <name> <url>`");
+    }
+    for (name, url) in channels {
+        info!("{name}\t{url}");
+    }
+    Ok(())
+}
+
+/// Compares the packages of two channels, leaving out packages that are the same in both.
+pub fn diff_channels(from: &Channel, to: &Channel) -> Vec<PackageDiff> {
+    let names = from
+        .pkg
+        .keys()
+        .chain(to.pkg.keys())
+        .collect::<BTreeSet<_>>();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let old = from.pkg.get(name);
+            let new = to.pkg.get(name);
+            let change = match (old, new) {
+                (None, Some(_)) => Change::Added,
+                (Some(_), None) => Change::Removed,
+                // Nightlies are ordered by the date of their build rather than its commit hash.
+                (Some(old), Some(new)) => match compare_versions(&old.version, &new.version) {
+                    Ordering::Less => Change::Upgraded,
+                    Ordering::Greater => Change::Downgraded,
+                    Ordering::Equal if new.fuels_version != old.fuels_version => {
+                        Change::FuelsVersionChanged
+                    }
+                    Ordering::Equal => return None,
+                },
+                (None, None) => return None,
+            };
+            Some(PackageDiff {
+                name: name.clone(),
+                change,
+                from_version: old.map(|p| p.version.clone()),
+                to_version: new.map(|p| p.version.clone()),
+                from_fuels_version: old.and_then(|p| p.fuels_version.clone()),
+                to_fuels_version: new.and_then(|p| p.fuels_version.clone()),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn format_package_diff(diff: &PackageDiff) -> String {
+    let version = match (&diff.from_version, &diff.to_version) {
+        (Some(from), Some(to)) if from != to => format!("{from} -> {to}"),
+        (Some(version), _) | (_, Some(version)) => version.to_string(),
+        (None, None) => String::new(),
+    };
+    let mut line = format!("- {} {version}", diff.name);
+    let in_both = !matches!(diff.change, Change::Added | Change::Removed);
+    if in_both && diff.from_fuels_version != diff.to_fuels_version {
+        line.push_str(&format!(
+            " (fuels {} -> {})",
+            diff.from_fuels_version.as_deref().unwrap_or("none"),
+            diff.to_fuels_version.as_deref().unwrap_or("none")
+        ));
+    }
+    line
+}
+
+pub fn diff(command: DiffCommand) -> Result<()> {
+    let DiffCommand { from, to, json } = command;
+
+    let fetch = |name: &str| -> Result<Channel> {
+        let description = DistToolchainDescription::from_str(name)?;
+        match Channel::from_dist_channel(&description) {
+            Ok(channel) => Ok(channel),
+            Err(e) => bail!("Could not read the channel of '{}': {}", name, e),
+        }
+    };
+    let packages = diff_channels(&fetch(&from)?, &fetch(&to)?);
+
+    if json {
+        let diff = ChannelDiff {
+            from: &from,
+            to: &to,
+            packages,
+        };
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+
+    if packages.is_empty() {
+        info!("{from} and {to} have the same packages");
+        return Ok(());
+    }
+
+    for (change, heading) in [
+        (Change::Added, "Added"),
+        (Change::Removed, "Removed"),
+        (Change::Upgraded, "Upgraded"),
+        (Change::Downgraded, "Downgraded"),
+        (
+            Change::FuelsVersionChanged,
+            "Compatible with a different fuels version",
+        ),
+    ] {
+        let lines = packages
+            .iter()
+            .filter(|p| p.change == change)
+            .map(format_package_diff)
+            .collect::<Vec<_>>();
+        if !lines.is_empty() {
+            info!("{heading}:\n{}", lines.join("\n"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_diff_channels() {
+        let from = Channel::from_toml(indoc! {r#"
+            [pkg.forc]
+            version = "0.49.0"
+            fuels_version = "0.60.0"
+            [pkg.forc.target.linux_amd64]
+            url = "https://example.com/forc.tar.gz"
+            hash = "00"
+
+            [pkg.fuel-core]
+            version = "0.22.0"
+            [pkg.fuel-core.target.linux_amd64]
+            url = "https://example.com/fuel-core.tar.gz"
+            hash = "00"
+
+            [pkg.forc-tx]
+            version = "0.1.0"
+            [pkg.forc-tx.target.linux_amd64]
+            url = "https://example.com/forc-tx.tar.gz"
+            hash = "00"
+
+            [pkg.forc-wallet]
+            version = "0.4.0"
+            fuels_version = "0.60.0"
+            [pkg.forc-wallet.target.linux_amd64]
+            url = "https://example.com/forc-wallet.tar.gz"
+            hash = "00"
+        "#})
+        .unwrap();
+        let to = Channel::from_toml(indoc! {r#"
+            [pkg.forc]
+            version = "0.50.0"
+            fuels_version = "0.61.0"
+            [pkg.forc.target.linux_amd64]
+            url = "https://example.com/forc.tar.gz"
+            hash = "00"
+
+            [pkg.fuel-core]
+            version = "0.21.0"
+            [pkg.fuel-core.target.linux_amd64]
+            url = "https://example.com/fuel-core.tar.gz"
+            hash = "00"
+
+            [pkg.forc-crypto]
+            version = "0.1.0"
+            [pkg.forc-crypto.target.linux_amd64]
+            url = "https://example.com/forc-crypto.tar.gz"
+            hash = "00"
+
+            [pkg.forc-wallet]
+            version = "0.4.0"
+            fuels_version = "0.61.0"
+            [pkg.forc-wallet.target.linux_amd64]
+            url = "https://example.com/forc-wallet.tar.gz"
+            hash = "00"
+        "#})
+        .unwrap();
+
+        let diff = diff_channels(&from, &to);
+        let changes = diff
+            .iter()
+            .map(|d| (d.name.as_str(), d.change))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                ("forc", Change::Upgraded),
+                ("forc-crypto", Change::Added),
+                ("forc-tx", Change::Removed),
+                ("forc-wallet", Change::FuelsVersionChanged),
+                ("fuel-core", Change::Downgraded),
+            ]
+        );
+        assert_eq!(
+            format_package_diff(&diff[0]),
+            "- forc 0.49.0 -> 0.50.0 (fuels 0.60.0 -> 0.61.0)"
+        );
+        assert_eq!(format_package_diff(&diff[1]), "- forc-crypto 0.1.0");
+        assert_eq!(
+            format_package_diff(&diff[3]),
+            "- forc-wallet 0.4.0 (fuels 0.60.0 -> 0.61.0)"
+        );
+        assert_eq!(
+            serde_json::to_value(Change::FuelsVersionChanged).unwrap(),
+            "fuels_version_changed"
+        );
+        assert!(diff_channels(&from, &from).is_empty());
+    }
+
+    #[test]
+    fn test_diff_channels_nightly_builds() {
+        let channel = |version: &str| {
+            Channel::from_toml(&format!(
+                indoc! {r#"
+                    [pkg.forc]
+                    version = "{}"
+                    [pkg.forc.target.linux_amd64]
+                    url = "https://example.com/forc.tar.gz"
+                    hash = "00"
+                "#},
+                version
+            ))
+            .unwrap()
+        };
+        let changes = |from: &str, to: &str| {
+            diff_channels(&channel(from), &channel(to))
+                .iter()
+                .map(|d| d.change)
+                .collect::<Vec<_>>()
+        };
+
+        // A later build is an upgrade whatever the commit hashes are, and a build from the same
+        // day but another commit is reported as one too.
+        let nightly = "0.24.3+nightly.20220915.ffffffff";
+        assert_eq!(
+            changes(nightly, "0.24.3+nightly.20220916.0b69f4d4"),
+            vec![Change::Upgraded]
+        );
+        assert_eq!(
+            changes("0.24.3+nightly.20220916.0b69f4d4", nightly),
+            vec![Change::Downgraded]
+        );
+        assert_eq!(
+            changes(nightly, "0.24.3+nightly.20220915.0b69f4d4"),
+            vec![Change::Upgraded]
+        );
+        assert!(changes(nightly, nightly).is_empty());
+    }
+}
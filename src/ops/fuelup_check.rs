@@ -2,10 +2,11 @@ use crate::{
     channel::Channel,
     commands::check::CheckCommand,
     config::Config,
-    download::get_latest_version,
+    download::latest_fuelup_version,
     file::get_bin_version,
     fmt::{bold, colored_bold},
     toolchain::{DistToolchainDescription, Toolchain},
+    versions::compare_versions,
 };
 use ansiterm::Color;
 use anyhow::Result;
@@ -66,7 +67,7 @@ fn check_plugin(
 ) {
     let version_or_err = match get_bin_version(plugin_executable) {
         Ok(version) => {
-            let order = version.cmp(latest_version);
+            let order = compare_versions(&version, latest_version);
             if order != Equal {
                 *num_updates += 1;
             }
@@ -81,8 +82,8 @@ fn check_plugin(
 
 fn check_fuelup(verbose: bool, max_length: usize) -> Result<()> {
     let fuelup_version: Version = Version::parse(clap::crate_version!())?;
-    if let Ok(latest) = get_latest_version(component::FUELUP) {
-        let order = fuelup_version.cmp(&latest);
+    if let Ok(latest) = latest_fuelup_version() {
+        let order = compare_versions(&fuelup_version, &latest);
         if verbose {
             let res = format_version_comparison(&order, &fuelup_version, &latest);
             info!("{} - {}", bold(component::FUELUP), res);
@@ -118,7 +119,7 @@ fn check_toolchain(toolchain: &str, verbose: bool) -> Result<u16> {
             let component_executable = toolchain.bin_path.join(&component.name);
             let version_text = match get_bin_version(&component_executable) {
                 Ok(version) => {
-                    let order = version.cmp(latest_version);
+                    let order = compare_versions(&version, latest_version);
                     format_version_comparison(&order, &version, latest_version)
                 }
                 Err(err) => err.to_string(),
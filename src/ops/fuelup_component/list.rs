@@ -1,7 +1,12 @@
-use crate::{download::get_latest_version, file::get_bin_version, fmt::bold, toolchain::Toolchain};
+use crate::{
+    file::get_bin_version,
+    fmt::bold,
+    toolchain::Toolchain,
+    versions::{compare_versions, LatestVersions},
+};
 use anyhow::Result;
 use component::Components;
-use std::fmt::Write;
+use std::{cmp::Ordering::Equal, fmt::Write};
 use tracing::info;
 
 fn format_installed_component_info(
@@ -36,22 +41,28 @@ pub fn list() -> Result<()> {
     let mut available_components_summary = String::from("Installable:\n");
 
     let components = Components::collect_publishables()?;
+    let versions = LatestVersions::new();
     for component in components {
-        let latest_version = get_latest_version(&component.name).map_or_else(
-            |_| String::from("failed to get latest version"),
+        let latest = versions.latest_version(&component.name).ok();
+        let latest_version = latest.as_ref().map_or_else(
+            || String::from("failed to get latest version"),
             |v| v.to_string(),
         );
         if toolchain.has_component(&component.name) {
             let exec_path = toolchain.bin_path.join(&component.name);
-            let current_version = get_bin_version(&exec_path).map(|v| v.to_string()).ok();
-            let version_info = match Some(&latest_version) == current_version.as_ref() {
+            let current_version = get_bin_version(&exec_path).ok();
+            let up_to_date = current_version
+                .as_ref()
+                .zip(latest.as_ref())
+                .is_some_and(|(current, latest)| compare_versions(current, latest) == Equal);
+            let version_info = match up_to_date {
                 true => "up-to-date".to_string(),
                 false => format!("latest: {}", &latest_version),
             };
 
             installed_components_summary.push_str(&format_installed_component_info(
                 &component.name,
-                current_version,
+                current_version.map(|v| v.to_string()),
                 &version_info,
             ));
 
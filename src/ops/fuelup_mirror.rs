@@ -0,0 +1,162 @@
+use crate::{
+    channel::{channel_path, construct_channel_url, resolve_url, Channel},
+    commands::mirror::MirrorCommand,
+    constants::FUELLABS_GITHUB_URL,
+    download::{dist_url, download, download_file, file_sha256, verify_checksum},
+    file::write_file,
+    path::ensure_dir_exists,
+    signature::verify_channel,
+    toolchain::DistToolchainDescription,
+};
+use anyhow::{anyhow, bail, Result};
+use std::{path::Path, str::FromStr};
+use toml_edit::{value, DocumentMut};
+use tracing::info;
+
+/// Directory within the mirror for artifacts that are not hosted on GitHub releases.
+const ARTIFACTS_DIR: &str = "artifacts";
+
+/// Returns the path of an artifact relative to the root of the mirror. Artifacts hosted on
+/// GitHub releases keep their layout so the mirror also works as a `FUELUP_DIST_SERVER`. Other
+/// hosts may serve different files under the same name, so their artifacts are kept apart by hash.
+fn artifact_path(url: &str, hash: &str) -> Result<String> {
+    let path = match url.strip_prefix(FUELLABS_GITHUB_URL) {
+        Some(path) => path.to_string(),
+        None => {
+            if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                bail!("Invalid hash '{}' for {}", hash, url);
+            }
+            let file_name = url.rsplit('/').next().unwrap_or(url);
+            format!("{ARTIFACTS_DIR}/{}/{file_name}", hash.to_ascii_lowercase())
+        }
+    };
+
+    // URLs come from the channel, so they must not be able to point outside of the mirror.
+    if path
+        .split('/')
+        .any(|segment| matches!(segment, "" | "." | "..") || segment.contains('\\'))
+    {
+        bail!("Refusing to mirror {} outside of its directory", url);
+    }
+    Ok(path)
+}
+
+fn mirror_artifact(url: &str, hash: &str, dst: &Path) -> Result<()> {
+    if dst.is_file() && file_sha256(dst)?.eq_ignore_ascii_case(hash) {
+        info!("{} is up to date", dst.display());
+        return Ok(());
+    }
+
+    if let Some(parent) = dst.parent() {
+        ensure_dir_exists(parent)?;
+    }
+
+    info!("Fetching {}", url);
+    let actual_hash = download_file(&dist_url(url), &dst.to_path_buf())?;
+    let file_name = dst.file_name().unwrap_or_default().to_string_lossy();
+    if let Err(e) = verify_checksum(&file_name, hash, &actual_hash) {
+        std::fs::remove_file(dst)?;
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+pub fn mirror(command: MirrorCommand) -> Result<()> {
+    let MirrorCommand { channel, dir } = command;
+
+    let description = DistToolchainDescription::from_str(&channel)?;
+    let channel_path = channel_path(&description)?;
+    let channel_url = dist_url(&construct_channel_url(&description)?);
+    let toml = match download(&channel_url) {
+        Ok(t) => t,
+        Err(_) => bail!("Could not read {}", &channel_url),
+    };
+    // The mirrored channel is rewritten, so the upstream signature is only checked here.
+    verify_channel(&channel_url, &toml)?;
+    let toml = String::from_utf8(toml)?;
+    // Fail early on manifests this version of fuelup can't read, rather than mirroring them.
+    Channel::from_toml(&toml)?;
+    let mut document = DocumentMut::from_str(&toml)?;
+
+    // Package URLs in the mirrored channel are relative to the channel itself.
+    let channel_base = channel_url.rsplit_once('/').map_or("", |(base, _)| base);
+    let to_root = "../".repeat(channel_path.matches('/').count());
+
+    let packages = document
+        .get_mut("pkg")
+        .and_then(|p| p.as_table_like_mut())
+        .ok_or_else(|| anyhow!("Channel {} does not contain any packages", &channel_url))?;
+    for (name, package) in packages.iter_mut() {
+        let Some(targets) = package
+            .get_mut("target")
+            .and_then(|t| t.as_table_like_mut())
+        else {
+            continue;
+        };
+        for (target, binary) in targets.iter_mut() {
+            let (Some(url), Some(hash)) = (
+                binary.get("url").and_then(|u| u.as_str()),
+                binary.get("hash").and_then(|h| h.as_str()),
+            ) else {
+                bail!(
+                    "Package '{}' is missing a url or hash for '{}'",
+                    name,
+                    target
+                );
+            };
+
+            let url = resolve_url(channel_base, url);
+            let relative_path = artifact_path(&url, hash)?;
+            mirror_artifact(&url, hash, &dir.join(&relative_path))?;
+            binary["url"] = value(format!("{to_root}{relative_path}"));
+        }
+    }
+
+    let channel_file = dir.join(&channel_path);
+    if let Some(parent) = channel_file.parent() {
+        ensure_dir_exists(parent)?;
+    }
+    write_file(&channel_file, &document.to_string())?;
+    info!("Mirrored '{}' to {}", channel, dir.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_path() {
+        let hash = "AB".repeat(32);
+        assert_eq!(
+            artifact_path(
+                "https://github.com/FuelLabs/forc/releases/download/v0.1.0/forc-0.1.0-linux_amd64.tar.gz",
+                &hash
+            )
+            .unwrap(),
+            "forc/releases/download/v0.1.0/forc-0.1.0-linux_amd64.tar.gz"
+        );
+        assert_eq!(
+            artifact_path(
+                "https://example.com/mirror/forc-0.1.0-linux_amd64.tar.gz",
+                &hash
+            )
+            .unwrap(),
+            format!(
+                "artifacts/{}/forc-0.1.0-linux_amd64.tar.gz",
+                "ab".repeat(32)
+            )
+        );
+
+        assert!(artifact_path(
+            "https://github.com/FuelLabs/../../etc/forc-0.1.0-linux_amd64.tar.gz",
+            &hash
+        )
+        .is_err());
+        assert!(artifact_path("https://github.com/FuelLabs//etc/forc.tar.gz", &hash).is_err());
+        assert!(artifact_path("https://example.com/mirror/..", &hash).is_err());
+        assert!(artifact_path("https://example.com/forc.tar.gz", "../../etc").is_err());
+    }
+}
@@ -2,13 +2,18 @@ use crate::{
     download::{download_file_and_unpack, unpack_bins, DownloadCfg},
     file::{get_bin_version, hard_or_symlink_file, read_file, write_file},
     fmt::{ask_user_yes_no_question, println_info},
-    path::{canonical_fuelup_dir, fuelup_bin, fuelup_bin_dir, fuelup_dir, FUELUP_DIR},
+    path::{
+        canonical_fuelup_dir, fuelup_bin, fuelup_bin_dir, fuelup_dir, is_default_fuelup_dir,
+        FUELUP_DIR, FUELUP_HOME,
+    },
     shell::Shell,
     target_triple::TargetTriple,
+    versions::compare_versions,
 };
 use anyhow::{bail, Context, Result};
 use component::{self, Components};
 use std::{
+    cmp::Ordering::Less,
     fs::{self, remove_dir_all},
     path::Path,
 };
@@ -22,44 +27,98 @@ pub fn attempt_install_self(download_cfg: DownloadCfg, dst: &Path) -> Result<()>
     Ok(())
 }
 
+/// Whether `path` is `fuelup_dir`. A relative `fuelup_dir`, ie. the default `.fuelup`, matches
+/// any directory of that name.
+fn points_at_fuelup_dir(path: &str, fuelup_dir: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    path == fuelup_dir
+        || (!Path::new(fuelup_dir).is_absolute() && path.ends_with(&format!("/{fuelup_dir}")))
+}
+
+/// Splits the value assigned in `line` after `assignment`, eg. `PATH=`, into the text before the
+/// value, the value and the text after it. Quotes around the value are kept with the outer parts.
+fn split_assignment<'a>(line: &'a str, assignment: &str) -> Option<(&'a str, &'a str, &'a str)> {
+    let start = line.find(assignment)? + assignment.len();
+    let (head, rest) = (line.get(..start)?, line.get(start..)?);
+    let (open, value) = match rest.chars().next() {
+        Some(quote @ ('"' | '\'')) => rest.split_at(quote.len_utf8()),
+        _ => ("", rest),
+    };
+    let end = match open.chars().next() {
+        Some(quote) => value.find(quote),
+        None => value.find(char::is_whitespace),
+    }
+    .unwrap_or(value.len());
+    let (value, tail) = value.split_at(end);
+    Some((line.get(..head.len() + open.len())?, value, tail))
+}
+
+/// Removes `fuelup_dir` and its `bin` directory from PATH definitions in `file_content`, along
+/// with any `FUELUP_HOME` export pointing at `fuelup_dir`. PATH entries are matched as whole paths
+/// and definitions left with nothing but `$PATH` are removed.
 #[inline]
-fn remove_path_from_content(file_content: &str) -> (bool, String) {
+fn remove_path_from_content(file_content: &str, fuelup_dir: &str) -> (bool, String) {
+    let is_fuelup_entry = |entry: &str| {
+        points_at_fuelup_dir(entry, fuelup_dir)
+            || entry
+                .trim_end_matches('/')
+                .strip_suffix("/bin")
+                .is_some_and(|dir| points_at_fuelup_dir(dir, fuelup_dir))
+    };
+    let home_definition = format!("export {FUELUP_HOME}=");
+
     let mut is_modified = false;
-    let whole_definition = format!("PATH={FUELUP_DIR}");
-    let suffix = format!("{FUELUP_DIR}:");
-    let prefix = format!("{FUELUP_DIR}:");
-    let lines = file_content
+    let mut new_lines = Vec::new();
+    for line in file_content
         .trim_end_matches('\n')
         .trim_end_matches('\r')
         .lines()
-        .collect::<Vec<_>>();
-    let total_lines = lines.len();
-    let new_lines = lines
-        .into_iter()
-        .filter(|line| !line.trim_end().ends_with(&whole_definition))
-        .map(|line| {
-            if line.contains("PATH") && line.contains(FUELUP_DIR) {
-                is_modified = true;
-                line.trim()
-                    .replace(&suffix, "")
-                    .replace(&prefix, "")
-                    .replace(FUELUP_DIR, "")
-            } else {
-                line.to_owned()
+    {
+        if line.trim_start().starts_with(&home_definition) {
+            if let Some((_, home, _)) = split_assignment(line, &home_definition) {
+                if points_at_fuelup_dir(home, fuelup_dir) {
+                    is_modified = true;
+                    continue;
+                }
             }
-        })
-        .collect::<Vec<_>>();
-    (
-        is_modified || total_lines != new_lines.len(),
-        new_lines.join("\n"),
-    )
+        }
+        let Some((head, value, tail)) = split_assignment(line, "PATH=") else {
+            new_lines.push(line.to_owned());
+            continue;
+        };
+        let entries = value.split(':').collect::<Vec<_>>();
+        if !entries.iter().any(|entry| is_fuelup_entry(entry)) {
+            new_lines.push(line.to_owned());
+            continue;
+        }
+        is_modified = true;
+        let kept = entries
+            .into_iter()
+            .filter(|entry| !is_fuelup_entry(entry))
+            .collect::<Vec<_>>();
+        if kept
+            .iter()
+            .any(|entry| !matches!(*entry, "$PATH" | "${PATH}"))
+        {
+            new_lines.push(format!("{head}{}{tail}", kept.join(":")));
+        }
+    }
+    (is_modified, new_lines.join("\n"))
 }
 
 /// Removes the fuelup directory from $PATH
 fn remove_fuelup_from_path() -> Result<()> {
+    let fuelup_dir = fuelup_dir();
+    let fuelup_dir = if is_default_fuelup_dir(&fuelup_dir) {
+        FUELUP_DIR.to_string()
+    } else {
+        fuelup_dir.to_string_lossy().into_owned()
+    };
+
     for shell in Shell::all() {
         for rc in shell.rc_files().into_iter().filter(|c| c.is_file()) {
-            let (was_modified, new_content) = remove_path_from_content(&read_file("rcfile", &rc)?);
+            let (was_modified, new_content) =
+                remove_path_from_content(&read_file("rcfile", &rc)?, &fuelup_dir);
             if was_modified {
                 println_info(format!(
                     "{} has been updated to remove fuelup from $PATH",
@@ -113,7 +172,9 @@ pub fn self_update(force: bool) -> Result<()> {
     let fuelup_bin = fuelup_bin();
     let fuelup_version = get_bin_version(&fuelup_bin).ok();
 
-    if !force && fuelup_version >= Some(download_cfg.version.clone()) {
+    let up_to_date = fuelup_version
+        .is_some_and(|version| compare_versions(&version, &download_cfg.version) != Less);
+    if !force && up_to_date {
         info!(
             "Already up to date (fuelup v{})",
             download_cfg.version.to_string()
@@ -202,14 +263,14 @@ mod test {
     #[test]
     fn dont_modify() {
         let content = "test\n\nbar\n";
-        let (was_modified, _) = remove_path_from_content(content);
+        let (was_modified, _) = remove_path_from_content(content, FUELUP_DIR);
         assert!(!was_modified);
     }
 
     #[test]
     fn remove_only_path() {
         let content = format!("test\nPATH={}\nbar\n", FUELUP_DIR);
-        let (was_modified, content) = remove_path_from_content(&content);
+        let (was_modified, content) = remove_path_from_content(&content, FUELUP_DIR);
         assert!(was_modified);
         assert_eq!("test\nbar", content);
     }
@@ -217,8 +278,36 @@ mod test {
     #[test]
     fn remove_subparts() {
         let content = format!("test\nPATH={}:foo\nbar\n", FUELUP_DIR);
-        let (was_modified, content) = remove_path_from_content(&content);
+        let (was_modified, content) = remove_path_from_content(&content, FUELUP_DIR);
         assert!(was_modified);
         assert_eq!("test\nPATH=foo\nbar", content);
     }
+
+    #[test]
+    fn remove_custom_fuelup_home() {
+        let content = "test\nexport FUELUP_HOME=\"/opt/fuel\"\nPATH=/opt/fuel\nbar\n";
+        let (was_modified, content) = remove_path_from_content(content, "/opt/fuel");
+        assert!(was_modified);
+        assert_eq!("test\nbar", content);
+    }
+
+    #[test]
+    fn keep_other_fuelup_home() {
+        let content = "export FUELUP_HOME=\"/opt/fuel-old\"\nexport PATH=\"/opt/fuel/bin:$PATH\"";
+        let (was_modified, content) = remove_path_from_content(content, "/opt/fuel");
+        assert!(was_modified);
+        assert_eq!("export FUELUP_HOME=\"/opt/fuel-old\"", content);
+    }
+
+    #[test]
+    fn remove_whole_path_entries() {
+        let content = "export PATH=\"$HOME/.fuelup/bin:$HOME/.fuelup-tools/bin:$PATH\"\n";
+        let (was_modified, content) = remove_path_from_content(content, FUELUP_DIR);
+        assert!(was_modified);
+        assert_eq!("export PATH=\"$HOME/.fuelup-tools/bin:$PATH\"", content);
+
+        let content = "PATH=/opt/fuel2/bin:/opt/fuel/tools:$PATH";
+        let (was_modified, _) = remove_path_from_content(content, "/opt/fuel");
+        assert!(!was_modified);
+    }
 }
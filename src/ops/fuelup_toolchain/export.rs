@@ -84,7 +84,9 @@ fn parse_toolchain_channel(toolchain_name: &str) -> Result<Channel> {
                     })
                 }
             }
-            DistToolchainName::Testnet | DistToolchainName::Mainnet => {
+            DistToolchainName::Testnet
+            | DistToolchainName::Mainnet
+            | DistToolchainName::Custom(_) => {
                 // These are dateless channels
                 Ok(Channel {
                     name: desc.name.to_string(),
@@ -3,19 +3,98 @@ use crate::settings::SettingsFile;
 use crate::toolchain::{DistToolchainDescription, Toolchain};
 use crate::{channel::Channel, commands::toolchain::InstallCommand};
 use anyhow::{bail, Result};
-use std::{fmt::Write, str::FromStr};
+use std::{fmt::Write, path::Path, str::FromStr};
 use tracing::{error, info};
 
+/// Whether `name` refers to a channel file or URL rather than a distributable toolchain. Files
+/// must be named by a path with a separator or a `.toml` extension, so that a file in the current
+/// directory that happens to share a toolchain's name isn't picked up instead.
+fn is_channel_source(name: &str) -> bool {
+    name.contains("://") || name.ends_with(".toml") || name.contains(std::path::is_separator)
+}
+
 pub fn install(command: InstallCommand) -> Result<()> {
-    let InstallCommand { name } = command;
+    let InstallCommand {
+        name,
+        toolchain_name,
+        channel_file,
+        artifacts,
+        offline,
+    } = command;
+
+    if is_channel_source(&name) {
+        if channel_file.is_some() {
+            bail!("--channel-file cannot be used when installing from a channel file or URL");
+        }
+        let Some(toolchain_name) = toolchain_name else {
+            bail!("--name is required when installing from a channel file or URL");
+        };
+        let channel = if name.contains("://") {
+            Channel::from_url(&name)
+        } else {
+            Channel::from_file(Path::new(&name))
+        };
+        let channel = match channel {
+            Ok(channel) => channel,
+            Err(e) => bail!("Could not build download configs from channel: {}", e),
+        };
+        return install_channel(&toolchain_name, channel, artifacts.as_deref(), offline);
+    } else if toolchain_name.is_some() {
+        bail!("--name can only be used when installing from a channel file or URL");
+    }
 
     let description = DistToolchainDescription::from_str(&name)?;
+    let channel = match &channel_file {
+        Some(path) => Channel::from_file(path)?,
+        None => match Channel::from_dist_channel(&description) {
+            Ok(channel) => channel,
+            Err(e) => bail!("Could not build download configs from channel: {}", e),
+        },
+    };
+    install_channel(
+        &description.to_string(),
+        channel,
+        artifacts.as_deref(),
+        offline,
+    )
+}
+
+/// Installs the packages of `channel` as the toolchain `name`, reading tarballs from `artifacts`
+/// instead of downloading them if it is set. When `offline`, the install fails before anything is
+/// changed if any of the tarballs is missing from `artifacts`.
+fn install_channel(
+    name: &str,
+    channel: Channel,
+    artifacts: Option<&Path>,
+    offline: bool,
+) -> Result<()> {
+    let cfgs = match artifacts {
+        Some(dir) => channel
+            .build_download_configs()
+            .into_iter()
+            .map(|cfg| cfg.with_artifacts_dir(dir))
+            .collect(),
+        None => channel.build_download_configs(),
+    };
+    if offline {
+        let missing = cfgs
+            .iter()
+            .filter(|cfg| cfg.local_tarball().is_none_or(|path| !path.is_file()))
+            .map(|cfg| cfg.name.as_str())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            bail!(
+                "Cannot install offline, the artifacts of these components are missing: {}",
+                missing.join(", ")
+            );
+        }
+    }
 
     let settings_file = settings_file();
     if !settings_file.exists() {
         let settings = SettingsFile::new(settings_file);
         settings.with_mut(|s| {
-            s.default_toolchain = Some(description.to_string());
+            s.default_toolchain = Some(name.to_string());
             Ok(())
         })?;
     }
@@ -25,12 +104,7 @@ pub fn install(command: InstallCommand) -> Result<()> {
 
     warn_existing_fuel_executables()?;
 
-    let toolchain = Toolchain::from_path(&description.to_string());
-    let cfgs = if let Ok(channel) = Channel::from_dist_channel(&description) {
-        channel.build_download_configs()
-    } else {
-        bail!("Could not build download configs from channel")
-    };
+    let toolchain = Toolchain::from_path(name);
 
     info!(
         "Downloading: {}",
@@ -39,8 +113,8 @@ pub fn install(command: InstallCommand) -> Result<()> {
             .collect::<String>()
     );
 
-    for cfg in cfgs {
-        match toolchain.add_component(cfg) {
+    for result in toolchain.add_components(cfgs)? {
+        match result {
             Ok(cfg) => writeln!(installed_bins, "- {} {}", cfg.name, cfg.version)?,
             Err(e) => writeln!(errored_bins, "- {e}")?,
         };
@@ -60,3 +134,21 @@ pub fn install(command: InstallCommand) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_channel_source() {
+        assert!(is_channel_source(
+            "https://example.com/channel-fuel-mainnet.toml"
+        ));
+        assert!(is_channel_source("channel-fuel-mainnet.toml"));
+        assert!(is_channel_source("./my-channel"));
+        assert!(is_channel_source("/srv/channels/my-channel"));
+        assert!(!is_channel_source("nightly"));
+        assert!(!is_channel_source("nightly-2024-05-01"));
+        assert!(!is_channel_source("mainnet"));
+    }
+}
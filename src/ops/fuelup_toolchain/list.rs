@@ -0,0 +1,234 @@
+use crate::{
+    commands::toolchain::ListCommand,
+    config::Config,
+    file::get_bin_version,
+    fmt::bold,
+    store::Store,
+    toolchain::{DistToolchainDescription, Toolchain},
+    toolchain_override::ToolchainOverride,
+};
+use anyhow::Result;
+use component::Components;
+use indicatif::HumanBytes;
+use semver::Version;
+use serde::Serialize;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use time::OffsetDateTime;
+use tracing::info;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ToolchainKind {
+    Dist,
+    Custom,
+}
+
+#[derive(Debug, Serialize)]
+struct ComponentInfo {
+    name: String,
+    /// `None` if the version could not be read from the executable.
+    version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolchainInfo {
+    name: String,
+    kind: ToolchainKind,
+    default: bool,
+    #[serde(rename = "override")]
+    is_override: bool,
+    components: Vec<ComponentInfo>,
+    /// Bytes that uninstalling the toolchain would free.
+    size: u64,
+    /// The day the toolchain was installed, as YYYY-MM-DD.
+    installed: Option<String>,
+}
+
+/// Returns the size of the files under `path`. With `owned_only`, files that are hard links
+/// shared with another location, like executables linked from the store, are left out.
+fn disk_usage(path: &Path, owned_only: bool) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| Some((entry.path(), entry.path().symlink_metadata().ok()?)))
+        .map(|(path, metadata)| {
+            if metadata.is_dir() {
+                disk_usage(&path, owned_only)
+            } else if metadata.is_file() && !(owned_only && metadata.nlink() > 1) {
+                metadata.len()
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Returns the publishable components installed in `toolchain`, with the versions of their
+/// executables.
+fn installed_components(toolchain: &Toolchain) -> Result<Vec<(String, Option<Version>)>> {
+    Ok(Components::collect_publishables()?
+        .into_iter()
+        .filter(|component| toolchain.has_component(&component.name))
+        .map(|component| {
+            let version = get_bin_version(&toolchain.bin_path.join(&component.name)).ok();
+            (component.name, version)
+        })
+        .collect())
+}
+
+/// Returns the store directories that the components of a toolchain are linked from.
+fn store_paths(store: &Store, components: &[(String, Option<Version>)]) -> BTreeSet<PathBuf> {
+    components
+        .iter()
+        .filter_map(|(name, version)| Some(store.component_dir_path(name, version.as_ref()?)))
+        .collect()
+}
+
+/// `store_users` counts the toolchains that each store directory is linked to, so that the size
+/// only includes directories that uninstalling this toolchain would remove.
+fn toolchain_info(
+    toolchain: Toolchain,
+    components: Vec<(String, Option<Version>)>,
+    store: &Store,
+    store_users: &HashMap<PathBuf, usize>,
+    default: Option<&str>,
+    override_name: Option<&str>,
+) -> ToolchainInfo {
+    let size = disk_usage(&toolchain.path, true)
+        + store_paths(store, &components)
+            .iter()
+            .filter(|path| store_users.get(*path) == Some(&1))
+            .map(|path| disk_usage(path, false))
+            .sum::<u64>();
+
+    let installed = fs::metadata(&toolchain.path)
+        .and_then(|metadata| metadata.created().or_else(|_| metadata.modified()))
+        .ok()
+        .map(|time| OffsetDateTime::from(time).date().to_string());
+
+    ToolchainInfo {
+        kind: if toolchain.is_distributed() {
+            ToolchainKind::Dist
+        } else {
+            ToolchainKind::Custom
+        },
+        default: default == Some(toolchain.name.as_str()),
+        is_override: override_name == Some(toolchain.name.as_str()),
+        components: components
+            .into_iter()
+            .map(|(name, version)| ComponentInfo {
+                name,
+                version: version.map(|v| v.to_string()),
+            })
+            .collect(),
+        size,
+        installed,
+        name: toolchain.name,
+    }
+}
+
+fn format_toolchain(toolchain: &ToolchainInfo, name_width: usize) -> String {
+    let mut status = Vec::new();
+    if toolchain.default {
+        status.push("default");
+    }
+    if toolchain.is_override {
+        status.push("override");
+    }
+    let components = toolchain
+        .components
+        .iter()
+        .map(|c| format!("{} {}", c.name, c.version.as_deref().unwrap_or("unknown")))
+        .collect::<Vec<_>>();
+
+    format!(
+        "{:<name_width$}  {:<6}  {:>10}  {:<10}  {}\n  {}",
+        toolchain.name,
+        match toolchain.kind {
+            ToolchainKind::Dist => "dist",
+            ToolchainKind::Custom => "custom",
+        },
+        HumanBytes(toolchain.size).to_string(),
+        toolchain.installed.as_deref().unwrap_or("-"),
+        status.join(", "),
+        if components.is_empty() {
+            "no components".to_string()
+        } else {
+            components.join(", ")
+        }
+    )
+}
+
+pub fn list(command: ListCommand) -> Result<()> {
+    let ListCommand { json } = command;
+
+    let default = Toolchain::from_settings().ok().map(|t| t.name);
+    let override_name = ToolchainOverride::from_project_root().map(|to| {
+        let channel = to.cfg.toolchain.channel.to_string();
+        DistToolchainDescription::from_str(&channel).map_or(channel, |desc| desc.to_string())
+    });
+
+    // The versions of every toolchain's components are read once up front, as telling which store
+    // directories a toolchain shares with others needs those of all the others.
+    let store = Store::from_env()?;
+    let installed = Config::from_env()?
+        .list_toolchains()?
+        .into_iter()
+        .map(|name| {
+            let toolchain = Toolchain::from_path(&name);
+            let components = installed_components(&toolchain)?;
+            Ok((toolchain, components))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut store_users = HashMap::new();
+    for (_, components) in &installed {
+        for path in store_paths(&store, components) {
+            *store_users.entry(path).or_insert(0) += 1;
+        }
+    }
+
+    let toolchains = installed
+        .into_iter()
+        .map(|(toolchain, components)| {
+            toolchain_info(
+                toolchain,
+                components,
+                &store,
+                &store_users,
+                default.as_deref(),
+                override_name.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&toolchains)?);
+        return Ok(());
+    }
+
+    if toolchains.is_empty() {
+        info!("No toolchains are installed");
+        return Ok(());
+    }
+    let name_width = toolchains.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    info!(
+        "{}",
+        bold(&format!(
+            "{:<name_width$}  {:<6}  {:>10}  {:<10}  STATUS",
+            "NAME", "KIND", "SIZE", "INSTALLED"
+        ))
+    );
+    for toolchain in &toolchains {
+        info!("{}", format_toolchain(toolchain, name_width));
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use crate::channel::ChannelIndex;
+use crate::commands::toolchain::ListAvailableCommand;
+use crate::toolchain::DistToolchainName;
+use anyhow::Result;
+use std::str::FromStr;
+use tracing::info;
+
+pub fn list_available(command: ListAvailableCommand) -> Result<()> {
+    let ListAvailableCommand { channel, since } = command;
+
+    let name = DistToolchainName::from_str(&channel)?;
+    let index = ChannelIndex::from_dist(&name)?;
+    let dates = index
+        .dates
+        .iter()
+        .filter(|date| since.is_none_or(|since| **date >= since))
+        .collect::<Vec<_>>();
+
+    if dates.is_empty() {
+        match since {
+            Some(since) => info!("No {} channels were published since {}", name, since),
+            None => info!("No {} channels have been published", name),
+        }
+    } else {
+        for date in dates {
+            info!("{}-{}", name, date);
+        }
+    }
+
+    Ok(())
+}
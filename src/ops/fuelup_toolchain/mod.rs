@@ -1,4 +1,6 @@
 pub mod export;
 pub mod install;
+pub mod list;
+pub mod list_available;
 pub mod new;
 pub mod uninstall;
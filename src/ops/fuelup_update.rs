@@ -34,10 +34,9 @@ pub fn update() -> Result<()> {
         let description = DistToolchainDescription::from_str(&toolchain)?;
         info!("updating the '{}' toolchain", description);
 
-        let cfgs = if let Ok(channel) = Channel::from_dist_channel(&description) {
-            channel.build_download_configs()
-        } else {
-            bail!("Could not build download configs from channel")
+        let cfgs = match Channel::from_dist_channel(&description) {
+            Ok(channel) => channel.build_download_configs(),
+            Err(e) => bail!("Could not build download configs from channel: {}", e),
         };
 
         info!(
@@ -46,9 +45,9 @@ pub fn update() -> Result<()> {
                 .map(|c| c.name.clone() + " ")
                 .collect::<String>()
         );
-        for cfg in cfgs {
-            let toolchain = Toolchain::from_path(&description.to_string());
-            match toolchain.add_component(cfg) {
+        let dist_toolchain = Toolchain::from_path(&description.to_string());
+        for result in dist_toolchain.add_components(cfgs)? {
+            match result {
                 Ok(cfg) => installed_bins.push_str(&format!("  - {} {}\n", cfg.name, cfg.version)),
                 Err(e) => errored_bins.push_str(&format!("  - {e}\n")),
             };
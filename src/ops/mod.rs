@@ -1,7 +1,11 @@
+pub mod fuelup_bisect;
+pub mod fuelup_cache;
+pub mod fuelup_channel;
 pub mod fuelup_check;
 pub mod fuelup_completions;
 pub mod fuelup_component;
 pub mod fuelup_default;
+pub mod fuelup_mirror;
 pub mod fuelup_self;
 pub mod fuelup_show;
 pub mod fuelup_toolchain;
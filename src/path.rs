@@ -21,18 +21,41 @@ const CANONICAL_FUEL_HOME: &str = "$HOME/.fuelup";
 pub fn canonical_fuelup_dir() -> Result<Cow<'static, str>> {
     let path = fuelup_dir();
 
-    let default_fuelup_home = dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".fuelup");
-    Ok(if default_fuelup_home == path {
+    Ok(if is_default_fuelup_dir(&path) {
         CANONICAL_FUEL_HOME.into()
     } else {
         path.to_string_lossy().into_owned().into()
     })
 }
 
+/// The fuelup home used when `FUELUP_HOME` is not set, ie. `$HOME/.fuelup`.
+pub fn default_fuelup_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(FUELUP_DIR)
+}
+
+pub fn is_default_fuelup_dir(path: &Path) -> bool {
+    default_fuelup_dir() == path
+}
+
+/// The root of the fuelup installation. Every other path within this module is resolved from
+/// here, so setting `FUELUP_HOME` relocates the store, toolchains, settings, logs and binaries.
+/// Relative values of `FUELUP_HOME` are resolved against the current directory.
 pub fn fuelup_dir() -> PathBuf {
-    dirs::home_dir().unwrap().join(FUELUP_DIR)
+    match env::var_os(FUELUP_HOME) {
+        Some(home) if !home.is_empty() => {
+            let home = PathBuf::from(home);
+            if home.is_absolute() {
+                home
+            } else {
+                env::current_dir()
+                    .map(|dir| dir.join(&home))
+                    .unwrap_or(home)
+            }
+        }
+        _ => default_fuelup_dir(),
+    }
 }
 
 pub fn fuelup_bin_dir() -> PathBuf {
@@ -74,6 +97,16 @@ pub fn store_dir() -> PathBuf {
     fuelup_dir().join("store")
 }
 
+/// Verified tarballs kept for reuse by later installs, named by their SHA-256 hash.
+pub fn downloads_dir() -> PathBuf {
+    fuelup_dir().join("downloads")
+}
+
+/// Fetched channels and their signatures, kept with the validators needed to revalidate them.
+pub fn channel_cache_dir() -> PathBuf {
+    fuelup_dir().join("channel-cache")
+}
+
 pub fn fuelup_tmp_dir() -> PathBuf {
     fuelup_dir().join("tmp")
 }
@@ -1,7 +1,7 @@
 use crate::file;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::{cell::RefCell, path::PathBuf};
+use std::{cell::RefCell, collections::BTreeMap, path::PathBuf};
 use toml_edit::{de, ser, DocumentMut};
 
 pub struct SettingsFile {
@@ -67,6 +67,30 @@ impl SettingsFile {
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Settings {
     pub default_toolchain: Option<String>,
+    /// Root URL of a mirror serving channels and release artifacts, used in place of GitHub.
+    pub dist_server: Option<String>,
+    /// Number of times a failed download is attempted before giving up.
+    pub retry_attempts: Option<u32>,
+    /// Initial delay in milliseconds between download attempts, doubled after each failure.
+    pub retry_delay_ms: Option<u64>,
+    /// Seconds to wait for a connection to be established before failing a download.
+    pub connect_timeout_secs: Option<u64>,
+    /// Seconds to wait for data on an established connection before failing a download.
+    pub read_timeout_secs: Option<u64>,
+    /// Path to a PEM file of root certificates to trust in addition to the bundled ones.
+    pub ca_bundle: Option<PathBuf>,
+    /// Hex-encoded ed25519 public keys of private mirrors that may sign channels.
+    pub channel_public_keys: Option<Vec<String>>,
+    /// Whether to reject channels that are not signed by a trusted key.
+    pub require_signed_channels: Option<bool>,
+    /// Whether to keep verified tarballs in `downloads` so that later installs can reuse them.
+    pub download_cache: Option<bool>,
+    /// Channels registered with `fuelup channel add`, mapping their names to their URLs.
+    pub channels: Option<BTreeMap<String, String>>,
+    /// Seconds a fetched channel is used for before checking whether it has changed.
+    pub channel_ttl_secs: Option<u64>,
+    /// Whether to only use cached channels instead of fetching them.
+    pub offline: Option<bool>,
 }
 
 impl Settings {
@@ -140,6 +164,7 @@ mod tests {
 
         let settings = Settings {
             default_toolchain: Some("yet-another-default-toolchain".to_string()),
+            ..Default::default()
         };
 
         assert_eq!(settings.to_string().unwrap(), expected_toml);
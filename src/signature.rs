@@ -0,0 +1,168 @@
+//! Verification of the detached ed25519 signatures published next to channel manifests.
+//!
+//! A channel at `<url>` is signed by `<url>.sig`, which holds the hex-encoded signature of the
+//! channel's exact bytes. Public keys are hex-encoded as well.
+
+use crate::{
+    channel_cache::download_channel,
+    constants::{FUELUP_CHANNEL_PUBLIC_KEYS, FUELUP_REQUIRE_SIGNED_CHANNELS},
+    download::setting,
+};
+use anyhow::Result;
+use component::decode_hex;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use tracing::{debug, warn};
+
+/// The key official channels are signed with, compiled in from `FUELUP_CHANNEL_PUBLIC_KEY` when
+/// fuelup is built for release.
+const BUILTIN_PUBLIC_KEY: Option<&str> = option_env!("FUELUP_CHANNEL_PUBLIC_KEY");
+
+pub const SIGNATURE_EXTENSION: &str = "sig";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    #[error("Channel {url} is not signed, but a signature is required to verify it")]
+    Missing { url: String },
+    #[error("Channel {url} is signed, but no public keys are trusted to verify it")]
+    NoTrustedKeys { url: String },
+    #[error("Signature of channel {url} is malformed")]
+    Malformed { url: String },
+    #[error("Signature of channel {url} does not match any trusted public key")]
+    Invalid { url: String },
+}
+
+/// Returns the public keys channels may be signed with: the built-in key, plus the keys of
+/// private mirrors from `FUELUP_CHANNEL_PUBLIC_KEYS` (comma-separated) or the
+/// `channel_public_keys` key in settings.toml.
+fn trusted_keys() -> Vec<String> {
+    let extra = setting(FUELUP_CHANNEL_PUBLIC_KEYS, |s| {
+        s.channel_public_keys.as_ref().map(|keys| keys.join(","))
+    })
+    .unwrap_or_default();
+
+    BUILTIN_PUBLIC_KEY
+        .into_iter()
+        .chain(extra.split(','))
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Whether channels without a valid signature must be rejected, as set by
+/// `FUELUP_REQUIRE_SIGNED_CHANNELS` or the `require_signed_channels` key in settings.toml.
+fn signatures_required() -> bool {
+    setting(FUELUP_REQUIRE_SIGNED_CHANNELS, |s| {
+        s.require_signed_channels
+    })
+    .unwrap_or(false)
+}
+
+/// Verifies that `signature` is a hex-encoded signature of `message` by one of `keys`.
+fn verify_signature(
+    url: &str,
+    message: &[u8],
+    signature: &str,
+    keys: &[String],
+) -> Result<(), SignatureError> {
+    let signature = decode_hex(signature.trim()).ok_or_else(|| SignatureError::Malformed {
+        url: url.to_string(),
+    })?;
+
+    for key in keys {
+        let Some(public_key) = decode_hex(key) else {
+            warn!("Ignoring malformed channel public key '{}'", key);
+            continue;
+        };
+        if UnparsedPublicKey::new(&ED25519, &public_key)
+            .verify(message, &signature)
+            .is_ok()
+        {
+            return Ok(());
+        }
+    }
+
+    Err(SignatureError::Invalid {
+        url: url.to_string(),
+    })
+}
+
+/// Checks `channel`, the contents of the channel at `url`, against its detached signature. Unsigned
+/// channels are only accepted when no public keys are trusted and signed channels aren't required.
+pub(crate) fn verify_channel(url: &str, channel: &[u8]) -> Result<()> {
+    let keys = trusted_keys();
+    let required = signatures_required();
+
+    let signature = match download_channel(&format!("{url}.{SIGNATURE_EXTENSION}")) {
+        Ok(signature) => signature,
+        Err(_) if required || !keys.is_empty() => {
+            return Err(SignatureError::Missing { url: url.into() }.into())
+        }
+        Err(_) => {
+            debug!("Channel {} is not signed", url);
+            return Ok(());
+        }
+    };
+
+    if keys.is_empty() {
+        if required {
+            return Err(SignatureError::NoTrustedKeys { url: url.into() }.into());
+        }
+        debug!(
+            "Skipping signature check of {}: no trusted public keys",
+            url
+        );
+        return Ok(());
+    }
+
+    verify_signature(url, channel, &String::from_utf8_lossy(&signature), &keys)?;
+    debug!("Verified signature of {}", url);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use component::encode_hex;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    #[test]
+    fn test_verify_signature() {
+        let url = "https://example.com/channel-fuel-mainnet.toml";
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+        let other_key_pair = Ed25519KeyPair::from_seed_unchecked(&[8; 32]).unwrap();
+        let public_key = encode_hex(key_pair.public_key().as_ref());
+        let other_public_key = encode_hex(other_key_pair.public_key().as_ref());
+
+        let channel = b"[pkg.forc]\nversion = \"0.17.0\"\n";
+        let signature = encode_hex(key_pair.sign(channel).as_ref());
+
+        assert!(
+            verify_signature(url, channel, &signature, std::slice::from_ref(&public_key)).is_ok()
+        );
+        assert!(verify_signature(
+            url,
+            channel,
+            &format!("{signature}\n"),
+            &[other_public_key.clone(), public_key.clone()]
+        )
+        .is_ok());
+        assert!(matches!(
+            verify_signature(
+                url,
+                b"[pkg.forc]\n",
+                &signature,
+                std::slice::from_ref(&public_key)
+            ),
+            Err(SignatureError::Invalid { .. })
+        ));
+        assert!(matches!(
+            verify_signature(url, channel, &signature, &[other_public_key]),
+            Err(SignatureError::Invalid { .. })
+        ));
+        assert!(matches!(
+            verify_signature(url, channel, "not a signature", &[public_key]),
+            Err(SignatureError::Malformed { .. })
+        ));
+    }
+}
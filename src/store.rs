@@ -5,6 +5,7 @@ use std::{
     fs,
     io::Write,
     path::{Path, PathBuf},
+    time::Duration,
 };
 use tracing::{info, warn};
 
@@ -14,6 +15,12 @@ use crate::{
     path::{ensure_dir_exists, store_dir},
 };
 
+/// Components are unpacked to directories named with this prefix before being moved into place.
+const UNPACK_DIR_PREFIX: &str = ".";
+/// How long an unpack directory is left alone before it is assumed to be from an interrupted
+/// install.
+const STALE_UNPACK_DIR_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
 fn component_dirname(component_name: &str, version: &Version) -> String {
     format!("{component_name}-{version}")
 }
@@ -33,19 +40,78 @@ impl Store {
         &self.path
     }
 
+    /// Whether a component has been completely installed in the store. A directory left behind by
+    /// an interrupted install of an older fuelup holds no executables yet, or still holds the
+    /// directory it was unpacked to, and is reinstalled.
     pub(crate) fn has_component(&self, component_name: &str, version: &Version) -> bool {
-        let dirname = component_dirname(component_name, version);
-        self.path().join(dirname).exists()
+        let Ok(entries) = fs::read_dir(self.component_dir_path(component_name, version)) else {
+            return false;
+        };
+        let executables = Component::from_name(component_name)
+            .map(|c| c.executables)
+            .unwrap_or_default();
+        let mut has_executable = false;
+        for entry in entries {
+            let Ok(entry) = entry else {
+                return false;
+            };
+            if entry.path().is_dir() {
+                return false;
+            }
+            let name = entry.file_name();
+            has_executable |= executables.is_empty()
+                || executables
+                    .iter()
+                    .any(|e| name.to_str() == Some(e.as_str()));
+        }
+        has_executable
     }
 
     pub(crate) fn component_dir_path(&self, component_name: &str, version: &Version) -> PathBuf {
         self.path.join(component_dirname(component_name, version))
     }
 
+    /// Returns the paths of the files of a component that is already in the store.
+    pub(crate) fn component_files(
+        &self,
+        component_name: &str,
+        version: &Version,
+    ) -> Result<Vec<PathBuf>> {
+        fs::read_dir(self.component_dir_path(component_name, version))?
+            .map(|entry| Ok(entry?.path()))
+            .collect()
+    }
+
     // This function installs a component into a directory within '/.fuelup/store'.
     // The directory is named '<component_name>-<version>', eg. 'fuel-core-0.15.1'.
     pub(crate) fn install_component(&self, cfg: &DownloadCfg) -> Result<Vec<PathBuf>> {
-        let component_dir = self.component_dir_path(&cfg.name, &cfg.version);
+        let dirname = component_dirname(&cfg.name, &cfg.version);
+        let component_dir = self.path.join(&dirname);
+
+        // The component is unpacked next to its final location and moved into place once it is
+        // complete, so that a failed install never leaves a partial directory in the store for
+        // `has_component` to find. The temporary directory is removed when it goes out of scope.
+        self.remove_stale_unpack_dirs();
+        let unpack_dir = tempfile::Builder::new()
+            .prefix(&format!("{UNPACK_DIR_PREFIX}{dirname}-"))
+            .tempdir_in(&self.path)?;
+        download_file_and_unpack(cfg, unpack_dir.path())?;
+        let installed = unpack_bins(unpack_dir.path(), &component_dir)?;
+
+        if component_dir.exists() && !self.has_component(&cfg.name, &cfg.version) {
+            warn!(
+                "Replacing incomplete install at {}",
+                component_dir.display()
+            );
+            fs::remove_dir_all(&component_dir)?;
+        }
+        if let Err(e) = fs::rename(unpack_dir.path(), &component_dir) {
+            // Another fuelup process installed the same component first.
+            if !self.has_component(&cfg.name, &cfg.version) {
+                return Err(e.into());
+            }
+            return self.component_files(&cfg.name, &cfg.version);
+        }
 
         // Cache fuels_version for this component if show_fuels_version exists and is true.
         // We don't want this failure to block installation, so errors are ignored here.
@@ -60,17 +126,48 @@ impl Store {
             }
         };
 
-        ensure_dir_exists(&component_dir)?;
-        download_file_and_unpack(cfg, &component_dir)?;
-        // We ensure that component_dir exists above, so its parent must exist here.
-        unpack_bins(&component_dir, &component_dir)
+        Ok(installed)
+    }
+
+    /// Removes directories that components were being unpacked to when an install was interrupted.
+    /// Only ones that haven't changed for a while are removed, as they may belong to an install
+    /// that is still running.
+    fn remove_stale_unpack_dirs(&self) {
+        let Ok(entries) = fs::read_dir(&self.path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let is_stale = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > STALE_UNPACK_DIR_AGE);
+            let is_unpack_dir = entry.path().is_dir()
+                && entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(UNPACK_DIR_PREFIX);
+            if is_unpack_dir && is_stale {
+                if let Err(e) = fs::remove_dir_all(entry.path()) {
+                    warn!("Failed to remove {}: {}", entry.path().display(), e);
+                }
+            }
+        }
     }
 
     pub(crate) fn cache_fuels_version(&self, cfg: &DownloadCfg) -> Result<()> {
         let dirname = component_dirname(&cfg.name, &cfg.version);
         let component_dir = self.path().join(dirname);
 
-        if let Ok(fuels_version) = fetch_fuels_version(cfg) {
+        let fuels_version = match cfg.fuels_version() {
+            Some(fuels_version) => Ok(fuels_version.to_string()),
+            // Tarballs from local artifacts imply that we should not reach out to GitHub either.
+            None if cfg.is_local() => return Ok(()),
+            None => fetch_fuels_version(cfg),
+        };
+
+        if let Ok(fuels_version) = fuels_version {
             info!(
                 "Caching fuels version at {}",
                 component_dir.join(FUELS_VERSION_FILE).display()
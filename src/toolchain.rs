@@ -1,8 +1,9 @@
 use crate::{
     channel::{self, Channel},
-    constants::DATE_FORMAT,
+    constants::{DATE_FORMAT, MAX_CONCURRENT_DOWNLOADS},
     download::DownloadCfg,
     file::{get_bin_version, hard_or_symlink_file, is_executable},
+    fmt::format_toolchain_with_target,
     path::{
         ensure_dir_exists, fuelup_bin_dir, fuelup_bin_or_current_bin, fuelup_tmp_dir,
         settings_file, toolchain_bin_dir, toolchain_dir, toolchains_dir,
@@ -11,7 +12,7 @@ use crate::{
     store::Store,
     target_triple::TargetTriple,
 };
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use component::{self, Components};
 use std::{
     collections::VecDeque,
@@ -20,6 +21,11 @@ use std::{
     path::PathBuf,
     process::Command,
     str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
 };
 use time::Date;
 use tracing::{error, info};
@@ -35,12 +41,14 @@ pub const RESERVED_TOOLCHAIN_NAMES: &[&str] = &[
     channel::IGNITION,
 ];
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DistToolchainName {
     Latest,
     Nightly,
     Testnet,
     Mainnet,
+    /// A channel registered with `fuelup channel add`.
+    Custom(String),
 }
 
 impl fmt::Display for DistToolchainName {
@@ -50,6 +58,7 @@ impl fmt::Display for DistToolchainName {
             DistToolchainName::Nightly => write!(f, "{}", channel::NIGHTLY),
             DistToolchainName::Testnet => write!(f, "{}", channel::TESTNET),
             DistToolchainName::Mainnet => write!(f, "{}", channel::MAINNET),
+            DistToolchainName::Custom(name) => write!(f, "{name}"),
         }
     }
 }
@@ -62,6 +71,7 @@ impl FromStr for DistToolchainName {
             channel::NIGHTLY => Ok(Self::Nightly),
             channel::TESTNET => Ok(Self::Testnet),
             channel::MAINNET => Ok(Self::Mainnet),
+            _ if channel::custom_channel_url(s).is_some() => Ok(Self::Custom(s.to_string())),
             _ => bail!("Unknown name for toolchain: {}", s),
         }
     }
@@ -146,6 +156,22 @@ fn extract_target(parts: &mut VecDeque<&str>) -> Option<TargetTriple> {
     try_extract(parts, 3).or_else(|| try_extract(parts, 4))
 }
 
+/// Whether `name` can be used for a custom channel: descriptions are parsed from their end, so a
+/// name ending in something that looks like a date or a target would be split up.
+pub(crate) fn is_valid_channel_name(name: &str) -> bool {
+    let parts = name.split('-').collect::<VecDeque<_>>();
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && parts.iter().all(|part| !part.is_empty())
+        && extract_date(&mut parts.clone()).is_none()
+        && extract_target(&mut parts.clone()).is_none()
+        && parts
+            .front()
+            .is_some_and(|first| !RESERVED_TOOLCHAIN_NAMES.contains(first))
+}
+
 /// Parses a distributable toolchain description from a string.
 ///
 /// The supported formats are:
@@ -200,6 +226,38 @@ impl FromStr for DistToolchainDescription {
     }
 }
 
+/// Applies `f` to each of `items` on up to `workers` threads, returning the results in the same
+/// order as `items`.
+fn map_concurrently<T, R, F>(items: &[T], workers: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+
+    thread::scope(|s| {
+        for _ in 0..workers.clamp(1, items.len().max(1)) {
+            s.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = f(item);
+                results
+                    .lock()
+                    .expect("another worker panicked")
+                    .push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().expect("another worker panicked");
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
 fn cache_sway_std_libs(forc_bin_path: PathBuf) -> Result<()> {
     let fuelup_tmp_dir = fuelup_tmp_dir();
     ensure_dir_exists(&fuelup_tmp_dir)?;
@@ -281,6 +339,9 @@ impl Toolchain {
 
     pub fn is_distributed(&self) -> bool {
         RESERVED_TOOLCHAIN_NAMES.contains(&self.name.split_once('-').unwrap_or((&self.name, "")).0)
+            || channel::custom_channels()
+                .keys()
+                .any(|name| self.name == format_toolchain_with_target(name))
     }
 
     pub fn exists(&self) -> bool {
@@ -303,74 +364,115 @@ impl Toolchain {
     }
 
     pub fn add_component(&self, download_cfg: DownloadCfg) -> Result<DownloadCfg> {
+        self.add_components(vec![download_cfg])?
+            .pop()
+            .ok_or_else(|| anyhow!("No component was added"))?
+    }
+
+    /// Adds components to the toolchain, returning the result of adding each of them in the order
+    /// they were given. Components missing from the store are downloaded and unpacked concurrently,
+    /// but are linked into the toolchain one at a time, in order.
+    pub fn add_components(
+        &self,
+        download_cfgs: Vec<DownloadCfg>,
+    ) -> Result<Vec<Result<DownloadCfg>>> {
         // Pre-install checks: ensuring toolchain dir, fuelup bin dir, and fuelup exist
         ensure_dir_exists(&self.bin_path)?;
+        ensure_dir_exists(&fuelup_bin_dir())?;
 
-        let fuelup_bin_dir = fuelup_bin_dir();
-        ensure_dir_exists(&fuelup_bin_dir)?;
-
-        let fuelup_bin = fuelup_bin_or_current_bin();
         let store = Store::from_env()?;
 
-        if !store.has_component(&download_cfg.name, &download_cfg.version)
-            || !self.has_component(&download_cfg.name)
-        {
+        let installed = map_concurrently(&download_cfgs, MAX_CONCURRENT_DOWNLOADS, |cfg| {
+            let in_store = store.has_component(&cfg.name, &cfg.version);
+            if in_store && self.has_component(&cfg.name) {
+                return None;
+            }
             info!(
                 "\nAdding component {} v{} to '{}'",
-                &download_cfg.name, &download_cfg.version, self.name
+                &cfg.name, &cfg.version, self.name
             );
+            // Components installed for other toolchains only have to be linked. Installing them
+            // again would replace a store directory those toolchains are linked to.
+            Some(if in_store {
+                store.component_files(&cfg.name, &cfg.version)
+            } else {
+                store.install_component(cfg)
+            })
+        });
+
+        Ok(download_cfgs
+            .into_iter()
+            .zip(installed)
+            .map(|(cfg, installed)| self.link_component(&store, cfg, installed))
+            .collect())
+    }
+
+    /// Links the executables of a component into the toolchain, either from the result of
+    /// installing it into the store or, if it was already in the store, from its store directory.
+    fn link_component(
+        &self,
+        store: &Store,
+        download_cfg: DownloadCfg,
+        installed: Option<Result<Vec<PathBuf>>>,
+    ) -> Result<DownloadCfg> {
+        let fuelup_bin_dir = fuelup_bin_dir();
+        let fuelup_bin = fuelup_bin_or_current_bin();
 
-            match store.install_component(&download_cfg) {
-                Ok(downloaded) => {
-                    for bin in downloaded {
-                        if is_executable(bin.as_path()) {
-                            if let Some(exe_file_name) = bin.file_name() {
-                                // Link binary in store -> binary in the toolchain dir
+        match installed {
+            Some(Ok(downloaded)) => {
+                for bin in downloaded {
+                    if is_executable(bin.as_path()) {
+                        if let Some(exe_file_name) = bin.file_name() {
+                            // Link binary in store -> binary in the toolchain dir
+                            hard_or_symlink_file(
+                                bin.as_path(),
+                                &self.bin_path.join(exe_file_name),
+                            )?;
+                            if !fuelup_bin_dir.join(exe_file_name).exists() {
+                                // Link real 'fuelup' bin -> fake 'fuelup' that acts as
+                                // the installed component in ~/.fuelup/bin, eg. 'forc'
                                 hard_or_symlink_file(
-                                    bin.as_path(),
-                                    &self.bin_path.join(exe_file_name),
+                                    &fuelup_bin,
+                                    &fuelup_bin_dir.join(exe_file_name),
                                 )?;
-                                if !fuelup_bin_dir.join(exe_file_name).exists() {
-                                    // Link real 'fuelup' bin -> fake 'fuelup' that acts as
-                                    // the installed component in ~/.fuelup/bin, eg. 'forc'
-                                    hard_or_symlink_file(
-                                        &fuelup_bin,
-                                        &fuelup_bin_dir.join(exe_file_name),
-                                    )?;
-                                }
                             }
                         }
                     }
-
-                    // Little hack here to download core and std lib upon installing `forc`
-                    if download_cfg.name == component::FORC {
-                        cache_sway_std_libs(self.bin_path.join(component::FORC))?;
-                    };
                 }
-                Err(e) => bail!(
-                    "Could not add component {}({}): {}",
-                    &download_cfg.name,
-                    &download_cfg.version,
-                    e
-                ),
-            }
 
-            info!(
-                "Installed {} v{} for toolchain '{}'",
-                download_cfg.name, download_cfg.version, self.name
-            );
-        } else {
-            // We have to iterate here because `fuelup component add forc` has to account for
-            // other built-in plugins as well, eg. forc-fmt
-            for entry in std::fs::read_dir(
-                store.component_dir_path(&download_cfg.name, &download_cfg.version),
-            )? {
-                let entry = entry?;
-                let exe = entry.path();
-
-                if is_executable(exe.as_path()) {
-                    if let Some(exe_file_name) = exe.file_name() {
-                        hard_or_symlink_file(exe.as_path(), &self.bin_path.join(exe_file_name))?;
+                // Little hack here to download core and std lib upon installing `forc`. Tarballs
+                // from local artifacts imply that we should not reach out to GitHub either.
+                if download_cfg.name == component::FORC && !download_cfg.is_local() {
+                    cache_sway_std_libs(self.bin_path.join(component::FORC))?;
+                };
+
+                info!(
+                    "Installed {} v{} for toolchain '{}'",
+                    download_cfg.name, download_cfg.version, self.name
+                );
+            }
+            Some(Err(e)) => bail!(
+                "Could not add component {}({}): {}",
+                &download_cfg.name,
+                &download_cfg.version,
+                e
+            ),
+            None => {
+                // We have to iterate here because `fuelup component add forc` has to account for
+                // other built-in plugins as well, eg. forc-fmt
+                for entry in std::fs::read_dir(
+                    store.component_dir_path(&download_cfg.name, &download_cfg.version),
+                )? {
+                    let entry = entry?;
+                    let exe = entry.path();
+
+                    if is_executable(exe.as_path()) {
+                        if let Some(exe_file_name) = exe.file_name() {
+                            hard_or_symlink_file(
+                                exe.as_path(),
+                                &self.bin_path.join(exe_file_name),
+                            )?;
+                        }
                     }
                 }
             }
@@ -661,6 +763,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_valid_channel_name() {
+        for name in ["team", "my-team", "team_2", "forc-patched"] {
+            assert!(is_valid_channel_name(name), "{name}");
+        }
+        for name in [
+            "",
+            "latest",
+            "nightly-patched",
+            "team-",
+            "team/channel",
+            "team-2024-01-01",
+            "team-x86_64-unknown-linux-gnu",
+        ] {
+            assert!(!is_valid_channel_name(name), "{name}");
+        }
+    }
+
     #[test]
     fn test_parse_description_invalid_channel_error() {
         for channel in INVALID_CHANNELS {
@@ -741,4 +861,15 @@ mod tests {
         assert_eq!(date.to_string(), "2022-12-25");
         assert_eq!(parts.len(), 1); // Ensure only the date parts are consumed
     }
+
+    #[test]
+    fn test_map_concurrently_preserves_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let results = map_concurrently(&items, 4, |i| {
+            std::thread::sleep(std::time::Duration::from_millis(20 - i));
+            i * 2
+        });
+        assert_eq!(results, items.iter().map(|i| i * 2).collect::<Vec<_>>());
+        assert!(map_concurrently(&[] as &[u64], 4, |i| *i).is_empty());
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    channel::{is_dateless_distributed_toolchain, LATEST, NIGHTLY},
+    channel::{custom_channel_url, is_dateless_distributed_toolchain, LATEST, NIGHTLY},
     constants::{DATE_FORMAT, FUEL_TOOLCHAIN_TOML_FILE},
     download::DownloadCfg,
     file,
@@ -255,7 +255,7 @@ impl fmt::Display for Channel {
 impl FromStr for Channel {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> Result<Self> {
-        if is_dateless_distributed_toolchain(s) {
+        if is_dateless_distributed_toolchain(s) || custom_channel_url(s).is_some() {
             return Ok(Self {
                 name: s.to_string(),
                 date: None,
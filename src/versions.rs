@@ -0,0 +1,144 @@
+use crate::{
+    channel::{self, Channel},
+    download::latest_fuelup_version,
+    toolchain::DistToolchainDescription,
+};
+use anyhow::{anyhow, Result};
+use component::FUELUP;
+use semver::Version;
+use std::{
+    cell::OnceCell,
+    cmp::Ordering::{self, Equal, Greater, Less},
+    str::FromStr,
+};
+
+/// Returns the date and commit of a nightly build from its build metadata, as in
+/// `0.24.3+nightly.20220915.0b69f4d4`.
+fn nightly_build(version: &Version) -> Option<(u32, &str)> {
+    let mut parts = version.build.as_str().split('.');
+    if parts.next() != Some("nightly") {
+        return None;
+    }
+    let date = parts.next().filter(|d| d.len() == 8)?.parse().ok()?;
+    Some((date, parts.next().unwrap_or_default()))
+}
+
+/// Compares an installed version against the latest one, taking nightly builds into account:
+/// semver doesn't order build metadata meaningfully, so nightlies are ordered by the date in
+/// their metadata. Nightlies are built from the version of their last release, so they are newer
+/// than that release. Two nightlies built on the same day from different commits can't be ordered,
+/// and `current` is reported as outdated so that the other build is offered.
+pub fn compare_versions(current: &Version, latest: &Version) -> Ordering {
+    let order = current.cmp_precedence(latest);
+    if order != Equal {
+        return order;
+    }
+
+    match (nightly_build(current), nightly_build(latest)) {
+        (Some((current_date, current_commit)), Some((latest_date, latest_commit))) => {
+            match current_date.cmp(&latest_date) {
+                Equal if current_commit != latest_commit => Less,
+                order => order,
+            }
+        }
+        (Some(_), None) => Greater,
+        (None, Some(_)) => Less,
+        (None, None) => current.build.cmp(&latest.build),
+    }
+}
+
+/// Resolves the latest versions of components from the `latest` channel, which is fetched the
+/// first time a component is looked up and reused for the lookups after it.
+#[derive(Debug, Default)]
+pub struct LatestVersions {
+    // The error is kept as a message so that later lookups fail the same way without refetching.
+    channel: OnceCell<Result<Channel, String>>,
+}
+
+impl LatestVersions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a resolver answering from `channel` instead of fetching the `latest` channel.
+    pub fn from_channel(channel: Channel) -> Self {
+        Self {
+            channel: OnceCell::from(Ok(channel)),
+        }
+    }
+
+    fn channel(&self) -> Result<&Channel> {
+        self.channel
+            .get_or_init(|| {
+                DistToolchainDescription::from_str(channel::LATEST)
+                    .and_then(|description| Channel::from_dist_channel(&description))
+                    .map_err(|e| e.to_string())
+            })
+            .as_ref()
+            .map_err(|e| anyhow!("Failed to get 'latest' channel: {}", e))
+    }
+
+    /// Returns the latest version of the component `name`. fuelup itself isn't part of channels,
+    /// so its version is looked up from its GitHub releases instead.
+    pub fn latest_version(&self, name: &str) -> Result<Version> {
+        if name == FUELUP {
+            return latest_fuelup_version();
+        }
+        self.channel()?
+            .pkg
+            .get(name)
+            .map(|p| p.version.clone())
+            .ok_or_else(|| {
+                anyhow!("'{name}' is not a valid, downloadable package in the 'latest' channel.")
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn compare(current: &str, latest: &str) -> Ordering {
+        compare_versions(
+            &Version::parse(current).unwrap(),
+            &Version::parse(latest).unwrap(),
+        )
+    }
+
+    #[test]
+    fn compare_nightly_versions() {
+        let nightly = "0.24.3+nightly.20220915.0b69f4d4";
+        assert_eq!(compare(nightly, nightly), Equal);
+        assert_eq!(compare(nightly, "0.24.3+nightly.20220916.1a2b3c4d"), Less);
+        assert_eq!(
+            compare(nightly, "0.24.3+nightly.20220914.ffffffff"),
+            Greater
+        );
+        assert_eq!(compare(nightly, "0.24.3+nightly.20220915.1a2b3c4d"), Less);
+        assert_eq!(compare(nightly, "0.24.4+nightly.20220901.0b69f4d4"), Less);
+        assert_eq!(compare(nightly, "0.24.3"), Greater);
+        assert_eq!(compare("0.24.3", nightly), Less);
+        assert_eq!(compare("0.24.3", "0.24.3"), Equal);
+        assert_eq!(compare("0.24.3", "0.25.0"), Less);
+    }
+
+    #[test]
+    fn latest_version_from_channel() {
+        let channel = Channel::from_toml(indoc! {r#"
+            [pkg.forc]
+            version = "0.50.0"
+            [pkg.forc.target.linux_amd64]
+            url = "https://example.com/forc.tar.gz"
+            hash = "00"
+        "#})
+        .unwrap();
+        let versions = LatestVersions::from_channel(channel);
+
+        assert_eq!(
+            versions.latest_version("forc").unwrap(),
+            Version::new(0, 50, 0)
+        );
+        assert!(versions.latest_version("forc-unknown").is_err());
+    }
+}
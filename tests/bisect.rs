@@ -0,0 +1,81 @@
+pub mod testcfg;
+
+use anyhow::Result;
+use fuelup::constants::FUELUP_DIST_SERVER;
+use std::{fs, os::unix::fs::PermissionsExt};
+use testcfg::{dist_server, FuelupState};
+
+#[test]
+fn fuelup_bisect() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let channel = fs::read_to_string(server.channel_file()).unwrap();
+        let nightly_dir = server.root.join("channels").join("nightly");
+        for day in ["01", "02", "03", "04"] {
+            let dir = nightly_dir.join("2024").join("05").join(day);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("channel-fuel-nightly.toml"), &channel).unwrap();
+        }
+        fs::write(
+            nightly_dir.join("index.toml"),
+            r#"dates = ["2024-05-01", "2024-05-02", "2024-05-03", "2024-05-04"]"#,
+        )
+        .unwrap();
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &[
+                "bisect",
+                "--good",
+                "nightly-2024-05-01",
+                "--bad",
+                "nightly-2024-05-04",
+                "--",
+                "fuel-core",
+                "--unknown-flag-is-ignored",
+            ],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output.status.success());
+        assert!(output
+            .stdout
+            .contains("nightly-2024-05-04 is the first bad toolchain, after nightly-2024-05-03"));
+        assert!(output
+            .stdout
+            .contains("The channels of both toolchains have the same packages"));
+
+        // Commands are run with the test's restricted PATH, so the failing command is a script
+        // in the test home rather than `false`.
+        let fail = cfg.home.join("fail");
+        fs::write(&fail, "#!/bin/sh\nexit 1\n").unwrap();
+        fs::set_permissions(&fail, fs::Permissions::from_mode(0o755)).unwrap();
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &[
+                "bisect",
+                "--good",
+                "nightly-2024-05-01",
+                "--bad",
+                "nightly-2024-05-04",
+                "--",
+                fail.to_str().unwrap(),
+            ],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output
+            .stdout
+            .contains("nightly-2024-05-02 is the first bad toolchain, after nightly-2024-05-01"));
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_bisect_requires_dated_toolchains() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let output = cfg.fuelup(&[
+            "bisect", "--good", "nightly", "--bad", "mainnet", "--", "true",
+        ]);
+        assert!(output.stdout.contains("'nightly' is not a dated toolchain"));
+    })?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+pub mod testcfg;
+
+use anyhow::Result;
+use fuelup::{
+    channel,
+    constants::{FUELUP_DIST_SERVER, FUELUP_DOWNLOAD_CACHE},
+    fmt::format_toolchain_with_target,
+};
+use std::fs;
+use testcfg::{dist_server, FuelupState};
+
+#[test]
+fn fuelup_cache() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let env = [
+            (FUELUP_DIST_SERVER, server.url.as_str()),
+            (FUELUP_DOWNLOAD_CACHE, "true"),
+        ];
+
+        let output = cfg.exec_with_env("fuelup", &["toolchain", "install", channel::MAINNET], &env);
+        assert!(output.status.success());
+        let downloads = cfg.fuelup_dir().join("downloads");
+        assert_eq!(fs::read_dir(&downloads).unwrap().count(), 1);
+
+        // With the tarball gone from the server and the store, it can only come from the cache.
+        fs::remove_dir_all(server.root.join("fuel-core")).unwrap();
+        fs::remove_dir_all(cfg.fuelup_dir().join("store")).unwrap();
+        let output = cfg.exec_with_env("fuelup", &["toolchain", "install", channel::MAINNET], &env);
+        assert!(output.status.success());
+        assert!(output.stdout.contains("Using cached download of fuel-core"));
+        let toolchain = format_toolchain_with_target(channel::MAINNET);
+        assert!(cfg
+            .toolchain_bin_dir(&toolchain)
+            .join("fuel-core")
+            .is_file());
+
+        let output = cfg.fuelup(&["cache", "clean"]);
+        assert!(output.status.success());
+        assert!(output.stdout.contains("Removed 1 cached download"));
+        assert!(!downloads.exists());
+
+        let output = cfg.fuelup(&["cache", "clean"]);
+        assert!(output
+            .stdout
+            .contains("The download cache is already empty"));
+    })?;
+    Ok(())
+}
@@ -0,0 +1,96 @@
+pub mod testcfg;
+
+use anyhow::Result;
+use fuelup::{constants::FUELUP_DIST_SERVER, fmt::format_toolchain_with_target};
+use testcfg::{dist_server, FuelupState};
+
+#[test]
+fn fuelup_channel_add_and_install() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let channel = server.channel_file();
+
+        let output = cfg.fuelup(&["channel", "add", "team", channel.to_str().unwrap()]);
+        assert!(output.status.success());
+        assert!(output.stdout.contains("Added channel 'team'"));
+
+        let output = cfg.fuelup(&["channel", "list"]);
+        assert!(output.stdout.contains("team\tfile://"));
+
+        // Tarballs in the channel point at GitHub, so serve them from the local directory.
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "install", "team"],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output.status.success());
+        assert!(output.stdout.contains("Installed:\n- fuel-core 0.1.0"));
+        let toolchain = format_toolchain_with_target("team");
+        assert!(cfg
+            .toolchain_bin_dir(&toolchain)
+            .join("fuel-core")
+            .is_file());
+
+        let output = cfg.fuelup(&["channel", "remove", "team"]);
+        assert!(output.status.success());
+        assert!(output.stdout.contains("is still installed"));
+        let output = cfg.fuelup(&["channel", "list"]);
+        assert!(output.stdout.contains("No channels are registered"));
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_channel_add_reserved_name() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let channel = server.channel_file();
+
+        let output = cfg.fuelup(&["channel", "add", "latest", channel.to_str().unwrap()]);
+        assert!(output.stdout.contains("Invalid channel name 'latest'"));
+        let output = cfg.fuelup(&["channel", "list"]);
+        assert!(output.stdout.contains("No channels are registered"));
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_channel_diff() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let mainnet = std::fs::read_to_string(server.channel_file()).unwrap();
+        let testnet = mainnet.replace(r#"version = "0.1.0""#, r#"version = "0.2.0""#);
+        std::fs::write(server.root.join("channel-fuel-testnet.toml"), testnet).unwrap();
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["channel", "diff", "mainnet", "testnet"],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output.status.success());
+        assert!(output
+            .stdout
+            .contains("Upgraded:\n- fuel-core 0.1.0 -> 0.2.0"));
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["channel", "diff", "testnet", "mainnet", "--json"],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        let diff: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+        let package = diff.pointer("/packages/0").unwrap();
+        assert_eq!(package["name"], "fuel-core");
+        assert_eq!(package["change"], "downgraded");
+        assert_eq!(package["from_version"], "0.2.0");
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["channel", "diff", "mainnet", "mainnet"],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output
+            .stdout
+            .contains("mainnet and mainnet have the same packages"));
+    })?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+pub mod testcfg;
+
+use anyhow::Result;
+use fuelup::{channel, constants::FUELUP_DIST_SERVER, fmt::format_toolchain_with_target};
+use std::fs;
+use testcfg::{dist_server, FuelupState, DIST_FUEL_CORE_VERSION};
+
+#[test]
+fn fuelup_mirror() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let upstream = dist_server(cfg);
+        let mirror = cfg.home.join("mirror");
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["mirror", channel::MAINNET, mirror.to_str().unwrap()],
+            &[(FUELUP_DIST_SERVER, &upstream.url)],
+        );
+        assert!(output.status.success());
+
+        let release_path = format!("fuel-core/releases/download/v{DIST_FUEL_CORE_VERSION}");
+        let channel = fs::read_to_string(mirror.join("channel-fuel-mainnet.toml")).unwrap();
+        assert!(channel.contains(&format!("url = \"{release_path}/fuel-core-")));
+        assert_eq!(fs::read_dir(mirror.join(&release_path)).unwrap().count(), 1);
+
+        // Mirroring again only re-verifies the artifacts that are already on disk.
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["mirror", channel::MAINNET, mirror.to_str().unwrap()],
+            &[(FUELUP_DIST_SERVER, &upstream.url)],
+        );
+        assert!(output.status.success());
+        assert!(output.stdout.contains("is up to date"));
+
+        let server = format!("file://{}", mirror.display());
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "install", channel::MAINNET],
+            &[(FUELUP_DIST_SERVER, &server)],
+        );
+        assert!(output.status.success());
+        assert!(output.stdout.contains("Installed:\n- fuel-core 0.1.0"));
+
+        let toolchain = format_toolchain_with_target(channel::MAINNET);
+        assert!(cfg
+            .toolchain_bin_dir(&toolchain)
+            .join("fuel-core")
+            .is_file());
+    })?;
+    Ok(())
+}
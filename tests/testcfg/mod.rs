@@ -5,6 +5,7 @@ use component::Component;
 use fuelup::channel::{LATEST, NIGHTLY, TESTNET};
 use fuelup::constants::FUEL_TOOLCHAIN_TOML_FILE;
 use fuelup::file::hard_or_symlink_file;
+use fuelup::path::FUELUP_HOME;
 use fuelup::settings::SettingsFile;
 use fuelup::target_triple::TargetTriple;
 use fuelup::toolchain::Toolchain;
@@ -151,10 +152,21 @@ impl TestCfg {
     /// This invokes std::process::Command with some default environment variables
     /// set up nicely for testing fuelup and its managed binaries.
     pub fn exec(&mut self, proc_name: &str, args: &[&str]) -> TestOutput {
+        self.exec_with_env(proc_name, args, &[])
+    }
+
+    /// Like testcfg::exec(), with additional environment variables set for the process.
+    pub fn exec_with_env(
+        &mut self,
+        proc_name: &str,
+        args: &[&str],
+        envs: &[(&str, &str)],
+    ) -> TestOutput {
         let path = self.fuelup_bin_dirpath.join(proc_name);
         let output = Command::new(path)
             .args(args)
             .current_dir(&self.home)
+            .env_remove(FUELUP_HOME)
             .env("HOME", &self.home)
             .env("CARGO_HOME", self.home.join(".cargo"))
             .env(
@@ -167,6 +179,7 @@ impl TestCfg {
                 ),
             )
             .env("TERM", "dumb")
+            .envs(envs.iter().copied())
             .output()
             .expect("Failed to execute command");
         let stdout = String::from_utf8(output.stdout).unwrap();
@@ -368,6 +381,82 @@ pub fn setup_default_override_file(cfg: &TestCfg, component_name: Option<&str>)
     setup_override_file(toolchain_override.clone()).unwrap()
 }
 
+/// The version of `fuel-core` served by the mock distribution server.
+pub const DIST_FUEL_CORE_VERSION: &str = "0.1.0";
+
+/// Creates a gzipped tarball at `path` containing a single `<dirname>/<exe_name>` executable,
+/// returning its hex-encoded SHA-256 digest.
+pub fn create_tarball(path: &Path, dirname: &str, exe_name: &str, version: &str) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let script = format!("#!/bin/sh\n\necho {exe_name} {version}");
+    let mut header = tar::Header::new_gnu();
+    header.set_size(script.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        Vec::new(),
+        flate2::Compression::default(),
+    ));
+    builder.append_data(
+        &mut header,
+        format!("{dirname}/{exe_name}"),
+        script.as_bytes(),
+    )?;
+    let tarball = builder.into_inner()?.finish()?;
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(path, &tarball)?;
+    Ok(format!("{:x}", Sha256::digest(&tarball)))
+}
+
+/// Sets up a mock distribution server at `root`, laid out like the gh-pages branch and GitHub
+/// releases. It serves a `mainnet` channel that contains a single `fuel-core` package.
+fn setup_dist_server(root: &Path) -> Result<()> {
+    let target = TargetTriple::from_component("fuel-core")?;
+    let version = DIST_FUEL_CORE_VERSION;
+    let dirname = format!("fuel-core-{version}-{target}");
+    let tarball_name = format!("{dirname}.tar.gz");
+    let release_path = format!("fuel-core/releases/download/v{version}/{tarball_name}");
+    let hash = create_tarball(&root.join(&release_path), &dirname, "fuel-core", version)?;
+
+    let channel = format!(
+        r#"[pkg.fuel-core]
+version = "{version}"
+
+[pkg.fuel-core.target.{target}]
+url = "https://github.com/FuelLabs/{release_path}"
+hash = "{hash}"
+"#
+    );
+    fs::write(root.join("channel-fuel-mainnet.toml"), channel)?;
+    Ok(())
+}
+
+/// A mock distribution server set up in the test home by [`dist_server`].
+pub struct DistServer {
+    /// The directory the server's files are in.
+    pub root: PathBuf,
+    /// The `file://` URL of `root`, to set `FUELUP_DIST_SERVER` to.
+    pub url: String,
+}
+
+impl DistServer {
+    /// The `mainnet` channel served by the server.
+    pub fn channel_file(&self) -> PathBuf {
+        self.root.join("channel-fuel-mainnet.toml")
+    }
+}
+
+/// Sets up a mock distribution server in the test home with [`setup_dist_server`].
+pub fn dist_server(cfg: &TestCfg) -> DistServer {
+    let root = cfg.home.join("dist-server");
+    setup_dist_server(&root).unwrap();
+    let url = format!("file://{}", root.display());
+    DistServer { root, url }
+}
+
 /// Based on a given FuelupState, sets up a temporary directory with all the necessary mock
 /// files and directories and provides a TestCfg to test fuelup.
 pub fn setup(state: FuelupState, f: &dyn Fn(&mut TestCfg)) -> Result<()> {
@@ -4,8 +4,17 @@ pub mod testcfg;
 use anyhow::Result;
 use component::{Component, FORC};
 use expects::expect_files_exist;
-use fuelup::{channel, fmt::format_toolchain_with_target, target_triple::TargetTriple};
-use testcfg::{yesterday, FuelupState, ALL_BINS, CUSTOM_TOOLCHAIN_NAME, DATE};
+use fuelup::{
+    channel,
+    constants::{FUELUP_CHANNEL_PUBLIC_KEYS, FUELUP_DIST_SERVER},
+    fmt::format_toolchain_with_target,
+    path::FUELUP_HOME,
+    target_triple::TargetTriple,
+};
+use testcfg::{
+    dist_server, yesterday, FuelupState, ALL_BINS, CUSTOM_TOOLCHAIN_NAME, DATE,
+    DIST_FUEL_CORE_VERSION,
+};
 
 #[test]
 fn fuelup_toolchain_install_latest() -> Result<()> {
@@ -65,6 +74,400 @@ fn fuelup_toolchain_install_nightly_date() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn fuelup_toolchain_install_from_dist_server() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "install", channel::MAINNET],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output.status.success());
+        assert!(output.stdout.contains("Installed:\n- fuel-core 0.1.0"));
+
+        let toolchain = format_toolchain_with_target(channel::MAINNET);
+        assert!(cfg
+            .toolchain_bin_dir(&toolchain)
+            .join("fuel-core")
+            .is_file());
+        assert!(cfg
+            .fuelup_dir()
+            .join("store")
+            .join(format!("fuel-core-{DIST_FUEL_CORE_VERSION}"))
+            .join("fuel-core")
+            .is_file());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_links_component_from_store() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let channel_file = server.channel_file();
+
+        cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "install", channel::MAINNET],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+
+        // The component is already in the store, so it must not be downloaded again.
+        std::fs::remove_dir_all(server.root.join("fuel-core")).unwrap();
+        cfg.fuelup(&["cache", "clean"]);
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &[
+                "toolchain",
+                "install",
+                channel_file.to_str().unwrap(),
+                "--name",
+                "my-release",
+            ],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output.stdout.contains("Installed:\n- fuel-core 0.1.0"));
+        for toolchain in [
+            &format_toolchain_with_target(channel::MAINNET),
+            "my-release",
+        ] {
+            assert!(cfg.toolchain_bin_dir(toolchain).join("fuel-core").is_file());
+        }
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_repairs_incomplete_store_dir() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+
+        // Left behind by an install that was interrupted before the binaries were unpacked.
+        let store = cfg.fuelup_dir().join("store");
+        let component_dir = store.join(format!("fuel-core-{DIST_FUEL_CORE_VERSION}"));
+        std::fs::create_dir_all(component_dir.join("fuel-core-0.1.0-partial")).unwrap();
+        let stale_unpack_dir = store.join(".fuel-core-0.1.0-stale");
+        std::fs::create_dir_all(&stale_unpack_dir).unwrap();
+        let day_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(2 * 86400);
+        std::fs::File::open(&stale_unpack_dir)
+            .unwrap()
+            .set_modified(day_ago)
+            .unwrap();
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "install", channel::MAINNET],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output.stdout.contains("Installed:\n- fuel-core 0.1.0"));
+        assert!(component_dir.join("fuel-core").is_file());
+        assert!(!component_dir.join("fuel-core-0.1.0-partial").exists());
+        assert!(!stale_unpack_dir.exists());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_unsigned_channel_with_trusted_key() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let public_key = "ab".repeat(32);
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "install", channel::MAINNET],
+            &[
+                (FUELUP_DIST_SERVER, &server.url),
+                (FUELUP_CHANNEL_PUBLIC_KEYS, &public_key),
+            ],
+        );
+        assert!(output.stdout.contains("is not signed"));
+        assert!(!cfg
+            .toolchain_bin_dir(&format_toolchain_with_target(channel::MAINNET))
+            .exists());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_offline() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let channel_file = server.channel_file();
+        let artifacts = server.root.join(format!(
+            "fuel-core/releases/download/v{DIST_FUEL_CORE_VERSION}"
+        ));
+
+        let output = cfg.fuelup(&[
+            "toolchain",
+            "install",
+            channel::MAINNET,
+            "--offline",
+            "--channel-file",
+            channel_file.to_str().unwrap(),
+            "--artifacts",
+            artifacts.to_str().unwrap(),
+        ]);
+        assert!(output.status.success());
+        assert!(output.stdout.contains("Installed:\n- fuel-core 0.1.0"));
+
+        let toolchain = format_toolchain_with_target(channel::MAINNET);
+        assert!(cfg
+            .toolchain_bin_dir(&toolchain)
+            .join("fuel-core")
+            .is_file());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_offline_unreachable_server() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let channel_file = server.channel_file();
+        let artifacts = cfg.home.join("artifacts");
+        std::fs::create_dir(&artifacts).unwrap();
+        // Nothing listens on the discard port, so any request made would fail.
+        let unreachable = "http://127.0.0.1:9";
+        let args = [
+            "toolchain",
+            "install",
+            channel::MAINNET,
+            "--offline",
+            "--channel-file",
+            channel_file.to_str().unwrap(),
+            "--artifacts",
+            artifacts.to_str().unwrap(),
+        ];
+
+        let output = cfg.exec_with_env("fuelup", &args, &[(FUELUP_DIST_SERVER, unreachable)]);
+        assert!(output
+            .stdout
+            .contains("the artifacts of these components are missing: fuel-core"));
+        let toolchain = format_toolchain_with_target(channel::MAINNET);
+        assert!(!cfg.toolchain_bin_dir(&toolchain).exists());
+
+        let tarball = format!(
+            "fuel-core-{DIST_FUEL_CORE_VERSION}-{}.tar.gz",
+            TargetTriple::from_component("fuel-core").unwrap()
+        );
+        std::fs::copy(
+            server
+                .root
+                .join(format!(
+                    "fuel-core/releases/download/v{DIST_FUEL_CORE_VERSION}"
+                ))
+                .join(&tarball),
+            artifacts.join(&tarball),
+        )
+        .unwrap();
+
+        let output = cfg.exec_with_env("fuelup", &args, &[(FUELUP_DIST_SERVER, unreachable)]);
+        assert!(output.stdout.contains("Installed:\n- fuel-core 0.1.0"));
+        assert!(cfg
+            .toolchain_bin_dir(&toolchain)
+            .join("fuel-core")
+            .is_file());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_offline_checksum_mismatch() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let channel_file = server.channel_file();
+        let artifacts = server.root.join(format!(
+            "fuel-core/releases/download/v{DIST_FUEL_CORE_VERSION}"
+        ));
+        let channel = std::fs::read_to_string(&channel_file).unwrap();
+        let channel = regex::Regex::new(r#"hash = "[0-9a-f]+""#)
+            .unwrap()
+            .replace(&channel, format!(r#"hash = "{}""#, "0".repeat(64)));
+        std::fs::write(&channel_file, channel.as_ref()).unwrap();
+
+        let output = cfg.fuelup(&[
+            "toolchain",
+            "install",
+            channel::MAINNET,
+            "--offline",
+            "--channel-file",
+            channel_file.to_str().unwrap(),
+            "--artifacts",
+            artifacts.to_str().unwrap(),
+        ]);
+        assert!(output.stdout.contains("Checksum mismatch"));
+
+        let toolchain = format_toolchain_with_target(channel::MAINNET);
+        assert!(!cfg.toolchain_bin_dir(&toolchain).join("fuel-core").exists());
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_offline_requires_channel_file_and_artifacts() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let output = cfg.fuelup(&["toolchain", "install", channel::MAINNET, "--offline"]);
+        assert!(!output.status.success());
+        assert!(output.stderr.contains("--channel-file <CHANNEL_FILE>"));
+        assert!(output.stderr.contains("--artifacts <ARTIFACTS>"));
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_channel_file() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let channel_file = server.channel_file();
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &[
+                "toolchain",
+                "install",
+                channel_file.to_str().unwrap(),
+                "--name",
+                "my-release",
+            ],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output.status.success());
+        assert!(output.stdout.contains("Installed:\n- fuel-core 0.1.0"));
+        assert!(cfg
+            .toolchain_bin_dir("my-release")
+            .join("fuel-core")
+            .is_file());
+
+        let output = cfg.fuelup(&["toolchain", "install", channel_file.to_str().unwrap()]);
+        assert!(output
+            .stdout
+            .contains("--name is required when installing from a channel file or URL"));
+
+        let output = cfg.fuelup(&[
+            "toolchain",
+            "install",
+            channel_file.to_str().unwrap(),
+            "--name",
+            channel::MAINNET,
+        ]);
+        assert!(!output.status.success());
+
+        let output = cfg.fuelup(&[
+            "toolchain",
+            "install",
+            channel_file.to_str().unwrap(),
+            "--name",
+            "other-release",
+            "--channel-file",
+            channel_file.to_str().unwrap(),
+        ]);
+        assert!(output
+            .stdout
+            .contains("--channel-file cannot be used when installing from a channel file or URL"));
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_list_available() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let server = dist_server(cfg);
+        let index_dir = server.root.join("channels").join(channel::NIGHTLY);
+        std::fs::create_dir_all(&index_dir).unwrap();
+        std::fs::write(
+            index_dir.join("index.toml"),
+            r#"dates = ["2024-05-01", "2024-05-03", "2024-05-10"]"#,
+        )
+        .unwrap();
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "list-available", channel::NIGHTLY],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert_eq!(
+            output.stdout,
+            "nightly-2024-05-01\nnightly-2024-05-03\nnightly-2024-05-10\n"
+        );
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &[
+                "toolchain",
+                "list-available",
+                channel::NIGHTLY,
+                "--since",
+                "2024-05-02",
+            ],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert_eq!(output.stdout, "nightly-2024-05-03\nnightly-2024-05-10\n");
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "install", "nightly-2024-05-04"],
+            &[(FUELUP_DIST_SERVER, &server.url)],
+        );
+        assert!(output
+            .stdout
+            .contains("the nearest date available is 2024-05-03"));
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_list() -> Result<()> {
+    testcfg::setup(FuelupState::LatestAndCustomInstalled, &|cfg| {
+        let latest = format_toolchain_with_target("latest");
+
+        let output = cfg.fuelup(&["toolchain", "list"]);
+        assert!(output.status.success());
+        let mut lines = output.stdout.lines();
+        assert!(lines.next().unwrap().contains("NAME"));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with(&latest));
+        assert!(row.contains("dist"));
+        assert!(row.ends_with("default"));
+        assert!(lines.next().unwrap().contains("forc 0.1.0"));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with(CUSTOM_TOOLCHAIN_NAME));
+        assert!(row.contains("custom"));
+    })?;
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_list_json() -> Result<()> {
+    testcfg::setup(FuelupState::LatestAndCustomInstalled, &|cfg| {
+        let output = cfg.fuelup(&["toolchain", "list", "--json"]);
+        assert!(output.status.success());
+        let toolchains: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+
+        let latest = toolchains.get(0).unwrap();
+        assert_eq!(latest["name"], format_toolchain_with_target("latest"));
+        assert_eq!(latest["kind"], "dist");
+        assert_eq!(latest["default"], true);
+        assert_eq!(latest["override"], false);
+        assert!(latest["size"].as_u64().unwrap() > 0);
+        assert!(latest["installed"].is_string());
+        assert!(latest["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|c| c["name"] == "forc" && c["version"] == "0.1.0"));
+
+        let custom = toolchains.get(1).unwrap();
+        assert_eq!(custom["name"], CUSTOM_TOOLCHAIN_NAME);
+        assert_eq!(custom["kind"], "custom");
+        assert_eq!(custom["default"], false);
+    })?;
+    Ok(())
+}
+
 #[test]
 fn fuelup_toolchain_install_malformed_date() -> Result<()> {
     testcfg::setup(FuelupState::Empty, &|cfg| {
@@ -146,6 +549,28 @@ Default toolchain set to '{CUSTOM_TOOLCHAIN_NAME}'\n"
     Ok(())
 }
 
+#[test]
+fn fuelup_toolchain_new_with_fuelup_home() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let fuelup_home = cfg.home.join("custom-fuelup-home");
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "new", CUSTOM_TOOLCHAIN_NAME],
+            &[(FUELUP_HOME, fuelup_home.to_str().unwrap())],
+        );
+        assert!(output.status.success());
+        assert!(fuelup_home
+            .join("toolchains")
+            .join(CUSTOM_TOOLCHAIN_NAME)
+            .join("bin")
+            .is_dir());
+        assert!(fuelup_home.join("settings.toml").is_file());
+        assert!(!cfg.toolchain_bin_dir(CUSTOM_TOOLCHAIN_NAME).exists());
+        assert!(!cfg.fuelup_dir().join("settings.toml").exists());
+    })?;
+    Ok(())
+}
+
 #[test]
 fn fuelup_toolchain_new_disallowed() -> Result<()> {
     testcfg::setup(FuelupState::Empty, &|cfg| {